@@ -0,0 +1,138 @@
+use std::fmt;
+
+/// An error converting rows or values to JSON.
+///
+/// Errors from the `row_value_to_json` functions don't know which column the value came from;
+/// those from the `rows_to_json` functions have `column` and `index` filled in.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The SQL type of a value has no conversion to JSON, with [`UnsupportedTypePolicy::Error`].
+    ///
+    /// [`UnsupportedTypePolicy::Error`]: crate::UnsupportedTypePolicy::Error
+    UnsupportedType {
+        /// Name of the column holding the value.
+        column: Option<String>,
+        /// Position of the column in the row, starting at 0.
+        index: Option<usize>,
+        /// Name of the SQL type, e.g. `"TSVECTOR"`.
+        type_name: String,
+    },
+    /// A value couldn't be decoded, in [`Options::strict`] mode or for a MySQL zero date with
+    /// [`InvalidDatePolicy::Error`].
+    ///
+    /// [`Options::strict`]: crate::Options::strict
+    /// [`InvalidDatePolicy::Error`]: crate::InvalidDatePolicy::Error
+    Decode {
+        /// Name of the column holding the value.
+        column: Option<String>,
+        /// Position of the column in the row, starting at 0.
+        index: Option<usize>,
+        /// Name of the SQL type of the value.
+        type_name: String,
+        /// Why decoding failed, always a [`sqlx::Error::Decode`].
+        source: sqlx::Error,
+    },
+    /// A value couldn't be taken from its row.
+    Column {
+        /// Name of the column.
+        column: String,
+        /// Position of the column in the row, starting at 0.
+        index: usize,
+        /// The error from sqlx.
+        source: sqlx::Error,
+    },
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+impl Error {
+    pub(crate) fn unsupported_type(type_name: &str) -> Self {
+        Error::UnsupportedType {
+            column: None,
+            index: None,
+            type_name: type_name.to_owned(),
+        }
+    }
+
+    pub(crate) fn decode(type_name: &str, source: impl Into<sqlx::error::BoxDynError>) -> Self {
+        // errors from sqlx's own decoding already are a `sqlx::Error`
+        let source = match source.into().downcast::<sqlx::Error>() {
+            Ok(source) => *source,
+            Err(source) => sqlx::Error::Decode(source),
+        };
+
+        Error::Decode {
+            column: None,
+            index: None,
+            type_name: type_name.to_owned(),
+            source,
+        }
+    }
+
+    /// Records the column a value came from.
+    pub(crate) fn in_column(mut self, name: &str, at: usize) -> Self {
+        match &mut self {
+            Error::UnsupportedType { column, index, .. } | Error::Decode { column, index, .. } => {
+                *column = Some(name.to_owned());
+                *index = Some(at);
+            }
+            Error::Column { .. } => {}
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (column, index) = match self {
+            Error::UnsupportedType {
+                type_name,
+                column,
+                index,
+            } => {
+                write!(f, "Unsupported type: {type_name}")?;
+                (column.as_deref(), *index)
+            }
+            Error::Decode {
+                type_name,
+                source,
+                column,
+                index,
+            } => {
+                // skip sqlx's "error occurred while decoding" prefix
+                match source {
+                    sqlx::Error::Decode(source) => {
+                        write!(f, "Failed to decode {type_name}: {source}")?
+                    }
+                    source => write!(f, "Failed to decode {type_name}: {source}")?,
+                }
+                (column.as_deref(), *index)
+            }
+            Error::Column {
+                column,
+                index,
+                source,
+            } => {
+                return write!(
+                    f,
+                    "Failed to get column `{column}` at index {index}: {source}"
+                );
+            }
+        };
+
+        match (column, index) {
+            (Some(column), Some(index)) => write!(f, " (column `{column}` at index {index})"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UnsupportedType { .. } => None,
+            Error::Decode { source, .. } | Error::Column { source, .. } => Some(source),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Well-known binary (WKB) geometries as [GeoJSON] geometry objects.
+//!
+//! Both ISO WKB and PostGIS' extended WKB (EWKB) are understood. Z coordinates are kept and
+//! M coordinates dropped, as GeoJSON has no place for them.
+//!
+//! [GeoJSON]: https://datatracker.ietf.org/doc/html/rfc7946
+
+use serde_json::{json, Value as JsonValue};
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// Converts a WKB geometry.
+///
+/// `srid` is used when the geometry doesn't embed one itself. With `crs`, a non-zero SRID is
+/// kept as a `crs` member naming the `EPSG:<srid>` coordinate system.
+///
+/// Returns `None` if `buf` isn't exactly one geometry.
+pub(crate) fn from_wkb(buf: &[u8], srid: Option<u32>, crs: bool) -> Option<JsonValue> {
+    let mut reader = Reader {
+        buf,
+        little_endian: false,
+    };
+
+    let (mut geometry, embedded_srid) = geometry(&mut reader)?;
+
+    if !reader.buf.is_empty() {
+        return None;
+    }
+
+    if let Some(srid) = embedded_srid.or(srid).filter(|srid| crs && *srid != 0) {
+        geometry["crs"] = json!({
+            "type": "name",
+            "properties": { "name": format!("EPSG:{srid}") },
+        });
+    }
+
+    Some(geometry)
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    little_endian: bool,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.buf.split_first_chunk()?;
+        self.buf = rest;
+        Some(*head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.take()?;
+
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        let bytes = self.take()?;
+
+        Some(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    /// Reads a count followed by that many items.
+    fn many<T>(&mut self, mut item: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let len = self.u32()?;
+        (0..len).map(|_| item(self)).collect()
+    }
+}
+
+/// Whether positions carry Z and M coordinates.
+#[derive(Clone, Copy)]
+struct Dims {
+    z: bool,
+    m: bool,
+}
+
+impl Dims {
+    fn position(self, reader: &mut Reader) -> Option<Vec<f64>> {
+        let mut position = vec![reader.f64()?, reader.f64()?];
+
+        if self.z {
+            position.push(reader.f64()?);
+        }
+
+        if self.m {
+            reader.f64()?;
+        }
+
+        Some(position)
+    }
+
+    fn positions(self, reader: &mut Reader) -> Option<Vec<Vec<f64>>> {
+        reader.many(|reader| self.position(reader))
+    }
+}
+
+/// Reads one geometry, returning it along with the SRID it embeds, if any.
+fn geometry(reader: &mut Reader) -> Option<(JsonValue, Option<u32>)> {
+    reader.little_endian = match reader.take()? {
+        [0] => false,
+        [1] => true,
+        _ => return None,
+    };
+
+    let header = reader.u32()?;
+
+    let srid = if header & EWKB_SRID != 0 {
+        Some(reader.u32()?)
+    } else {
+        None
+    };
+
+    // ISO WKB adds 1000 for Z, 2000 for M and 3000 for both to the type code instead
+    let code = header & 0x0FFF_FFFF;
+    let dims = Dims {
+        z: header & EWKB_Z != 0 || matches!(code / 1000, 1 | 3),
+        m: header & EWKB_M != 0 || matches!(code / 1000, 2 | 3),
+    };
+
+    let members = |reader: &mut Reader| -> Option<Vec<JsonValue>> {
+        reader.many(|reader| {
+            let (mut member, _) = geometry(reader)?;
+            Some(member["coordinates"].take())
+        })
+    };
+
+    let geometry = match code % 1000 {
+        1 => {
+            let position = dims.position(reader)?;
+
+            // an empty point is written as NaN coordinates
+            if position.iter().all(|n| n.is_nan()) {
+                json!({ "type": "Point", "coordinates": [] })
+            } else {
+                json!({ "type": "Point", "coordinates": position })
+            }
+        }
+        2 => json!({ "type": "LineString", "coordinates": dims.positions(reader)? }),
+        3 => {
+            let rings = reader.many(|reader| dims.positions(reader))?;
+            json!({ "type": "Polygon", "coordinates": rings })
+        }
+        4 => json!({ "type": "MultiPoint", "coordinates": members(reader)? }),
+        5 => json!({ "type": "MultiLineString", "coordinates": members(reader)? }),
+        6 => json!({ "type": "MultiPolygon", "coordinates": members(reader)? }),
+        7 => {
+            let geometries = reader.many(|reader| geometry(reader).map(|(member, _)| member))?;
+            json!({ "type": "GeometryCollection", "geometries": geometries })
+        }
+        // curves, surfaces and the like have no GeoJSON equivalent
+        _ => return None,
+    };
+
+    Some((geometry, srid))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// `POINT(1 2)` as little-endian ISO WKB.
+    pub(crate) const POINT: &str = "0101000000000000000000F03F0000000000000040";
+
+    /// `SRID=4326;POINT(1 2)` as little-endian EWKB.
+    const POINT_SRID: &str = "0101000020E6100000000000000000F03F0000000000000040";
+
+    /// `SRID=4326;POINT ZM (1 2 3 4)` as little-endian EWKB.
+    const POINT_ZM_SRID: &str = concat!(
+        "01010000E0E6100000",
+        "000000000000F03F0000000000000040",
+        "00000000000008400000000000001040",
+    );
+
+    /// `POINT EMPTY`, written as NaN coordinates.
+    const POINT_EMPTY: &str = "0101000000000000000000F87F000000000000F87F";
+
+    /// `GEOMETRYCOLLECTION(POINT(1 2), GEOMETRYCOLLECTION(LINESTRING(0 0, 1 1)))` as
+    /// big-endian WKB.
+    const NESTED_COLLECTION: &str = concat!(
+        "000000000700000002",
+        "00000000013FF00000000000004000000000000000",
+        "000000000700000001",
+        "00000000020000000200000000000000000000000000000000",
+        "3FF00000000000003FF0000000000000",
+    );
+
+    pub(crate) fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn point() {
+        let v = from_wkb(&hex(POINT), None, false);
+        assert_eq!(
+            v,
+            Some(json!({ "type": "Point", "coordinates": [1.0, 2.0] }))
+        );
+    }
+
+    #[test]
+    fn ewkb_srid() {
+        let v = from_wkb(&hex(POINT_SRID), None, false);
+        assert_eq!(
+            v,
+            Some(json!({ "type": "Point", "coordinates": [1.0, 2.0] }))
+        );
+
+        // the embedded SRID wins over the one given
+        let v = from_wkb(&hex(POINT_SRID), Some(3857), true).unwrap();
+        assert_eq!(v["crs"]["properties"]["name"], "EPSG:4326");
+    }
+
+    #[test]
+    fn ewkb_z_and_m() {
+        let v = from_wkb(&hex(POINT_ZM_SRID), None, false);
+        assert_eq!(
+            v,
+            Some(json!({ "type": "Point", "coordinates": [1.0, 2.0, 3.0] }))
+        );
+    }
+
+    #[test]
+    fn iso_type_codes() {
+        let xyz = "000000000000F03F00000000000000400000000000000840";
+        let xyzm = "000000000000F03F000000000000004000000000000008400000000000001040";
+
+        // POINT Z, POINT M and POINT ZM
+        let z = from_wkb(&hex(&format!("01E9030000{xyz}")), None, false).unwrap();
+        assert_eq!(z["coordinates"], json!([1.0, 2.0, 3.0]));
+
+        let m = from_wkb(&hex(&format!("01D1070000{xyz}")), None, false).unwrap();
+        assert_eq!(m["coordinates"], json!([1.0, 2.0]));
+
+        let zm = from_wkb(&hex(&format!("01B90B0000{xyzm}")), None, false).unwrap();
+        assert_eq!(zm["coordinates"], json!([1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn empty_point() {
+        let v = from_wkb(&hex(POINT_EMPTY), None, false);
+        assert_eq!(v, Some(json!({ "type": "Point", "coordinates": [] })));
+    }
+
+    #[test]
+    fn nested_geometry_collection() {
+        let v = from_wkb(&hex(NESTED_COLLECTION), None, false);
+        assert_eq!(
+            v,
+            Some(json!({
+                "type": "GeometryCollection",
+                "geometries": [
+                    { "type": "Point", "coordinates": [1.0, 2.0] },
+                    {
+                        "type": "GeometryCollection",
+                        "geometries": [
+                            { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] },
+                        ],
+                    },
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        assert_eq!(from_wkb(&hex(&format!("{POINT}00")), None, false), None);
+        assert_eq!(from_wkb(&hex(&POINT[..POINT.len() - 2]), None, false), None);
+    }
+}
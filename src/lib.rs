@@ -1,5 +1,20 @@
+mod error;
+mod options;
+
+#[cfg(any(feature = "mysql", feature = "postgis"))]
+mod geojson;
+
+pub use options::{
+    BinaryEncoding, BitFormat, DateTimeFormat, DecimalFormat, FallbackColumn, GeometryFormat,
+    IntervalFormat, InvalidDatePolicy, MySqlTimeFormat, Options, TimeZone, UnsupportedTypePolicy,
+    UuidFormat,
+};
+#[cfg(feature = "chrono")]
+pub use options::ChronoFormat;
+pub use error::Error;
+
 #[cfg(feature = "postgres")]
 pub mod postgres;
 
 #[cfg(feature = "mysql")]
-pub mod mysql;
\ No newline at end of file
+pub mod mysql;
@@ -1,8 +1,15 @@
 use std::collections::HashMap;
 
 use serde_json::Value as JsonValue;
-use sqlx::{mysql::{MySqlValueRef, MySqlRow}, Row, Column, TypeInfo, Value, ValueRef};
-use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+use sqlx::{
+    mysql::{MySql, MySqlRow, MySqlTypeInfo, MySqlValueRef},
+    Column, Decode, Row, Type, TypeInfo, Value, ValueRef,
+};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime};
+
+#[cfg(feature = "chrono")]
+use crate::{ChronoFormat, DateTimeFormat};
+use crate::{Error, FallbackColumn, InvalidDatePolicy, Options};
 
 /// A wrapper for [`row_value_to_json`] function.
 /// 
@@ -15,26 +22,91 @@ use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 /// let output = sqlx_to_json::postgres::rows_to_json(rows).unwrap();
 /// ```
 /// [`row_value_to_json`]: fn.row_value_to_json.html
-pub fn rows_to_json(rows: Vec<MySqlRow>) -> Result<Vec<HashMap<String, JsonValue>>, String> {
-    if rows.is_empty() {
-        return Ok(vec![]);
-    }
+pub fn rows_to_json(rows: Vec<MySqlRow>) -> Result<Vec<HashMap<String, JsonValue>>, Error> {
+    rows_to_json_with_options(rows, &Options::default())
+}
 
+/// Same as [`rows_to_json`] but with custom [`Options`].
+///
+/// # Example
+/// ```no_run
+/// # async fn example(mut conn: sqlx::MySqlConnection) {
+/// use sqlx_to_json::{DecimalFormat, Options};
+///
+/// let options = Options {
+///     decimal: DecimalFormat::Number,
+///     ..Default::default()
+/// };
+///
+/// let rows = sqlx::query("SELECT * FROM orders LIMIT 10").fetch_all(&mut conn).await.unwrap();
+/// let output = sqlx_to_json::mysql::rows_to_json_with_options(rows, &options).unwrap();
+/// # }
+/// ```
+pub fn rows_to_json_with_options(
+    rows: Vec<MySqlRow>,
+    options: &Options,
+) -> Result<Vec<HashMap<String, JsonValue>>, Error> {
+    rows_to_json_with_fallbacks(rows, options).map(|(output, _)| output)
+}
+
+/// Same as [`rows_to_json_with_options`], but also returns the columns that had values
+/// converted by the [`Options::unsupported_type`] fallback, in column order.
+///
+/// # Example
+/// ```no_run
+/// # async fn example(mut conn: sqlx::MySqlConnection) {
+/// use sqlx_to_json::{Options, UnsupportedTypePolicy};
+///
+/// let options = Options {
+///     unsupported_type: UnsupportedTypePolicy::Text,
+///     ..Default::default()
+/// };
+///
+/// let rows = sqlx::query("SELECT * FROM orders LIMIT 10").fetch_all(&mut conn).await.unwrap();
+/// let (output, fallbacks) =
+///     sqlx_to_json::mysql::rows_to_json_with_fallbacks(rows, &options).unwrap();
+///
+/// for fallback in fallbacks {
+///     eprintln!("column `{}` has unsupported type {}", fallback.column, fallback.type_name);
+/// }
+/// # }
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn rows_to_json_with_fallbacks(
+    rows: Vec<MySqlRow>,
+    options: &Options,
+) -> Result<(Vec<HashMap<String, JsonValue>>, Vec<FallbackColumn>), Error> {
     let mut output = Vec::with_capacity(rows.len());
+    let mut fallback_columns: Vec<FallbackColumn> = vec![];
+    // all rows of a result share their columns
+    let mut column_infos: Option<Vec<ColumnInfo>> = None;
 
     for row in rows {
         let mut map = HashMap::new();
+        let column_infos = column_infos.get_or_insert_with(|| {
+            row.columns().iter().map(|c| ColumnInfo::of(c.type_info())).collect()
+        });
 
         for (i, column) in row.columns().iter().enumerate() {
-            let row_value = row.try_get_raw(i).map_err(|e| e.to_string())?;
-            let value_json = row_value_to_json(row_value).map_err(|e| e.to_string())?;
+            let row_value = row.try_get_raw(i).map_err(|source| Error::Column {
+                column: column.name().to_owned(),
+                index: i,
+                source,
+            })?;
+            let mut fallbacks = vec![];
+            let value_json = value_to_json(row_value, &column_infos[i], options, &mut fallbacks)
+                .map_err(|e| e.in_column(column.name(), i))?;
+            FallbackColumn::record(&mut fallback_columns, column.name(), i, fallbacks);
+
             map.insert(column.name().to_string(), value_json);
         }
 
         output.push(map);
     }
 
-    Ok(output)
+    fallback_columns.sort_by_key(|c| c.index);
+
+    Ok((output, fallback_columns))
 }
 
 /// # Example
@@ -57,35 +129,75 @@ pub fn rows_to_json(rows: Vec<MySqlRow>) -> Result<Vec<HashMap<String, JsonValue
 ///     output.push(map);
 /// }
 /// ```
-pub fn row_value_to_json(row_value: MySqlValueRef) -> Result<JsonValue, String> {
+pub fn row_value_to_json(row_value: MySqlValueRef) -> Result<JsonValue, Error> {
+    row_value_to_json_with_options(row_value, &Options::default())
+}
+
+/// Same as [`row_value_to_json`] but with custom [`Options`].
+pub fn row_value_to_json_with_options(
+    row_value: MySqlValueRef,
+    options: &Options,
+) -> Result<JsonValue, Error> {
+    let column = ColumnInfo::of(&row_value.type_info());
+    value_to_json(row_value, &column, options, &mut vec![])
+}
+
+/// [`row_value_to_json_with_options`] with the [`ColumnInfo`] of the column already at hand,
+/// adding the types of the values converted by an [`UnsupportedTypePolicy`] fallback to
+/// `fallbacks`.
+///
+/// [`UnsupportedTypePolicy`]: crate::UnsupportedTypePolicy
+fn value_to_json(
+    row_value: MySqlValueRef,
+    column: &ColumnInfo,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
     if row_value.is_null() {
         return Ok(JsonValue::Null);
     }
 
+    #[cfg(feature = "chrono")]
+    if let DateTimeFormat::Chrono(format) = &options.datetime {
+        if let Some(res) = chrono_to_json(&row_value, format, options) {
+            return res;
+        }
+    }
+
     let res = match row_value.type_info().name() {
+        // SET columns are usually sent as CHAR, marked only by a column flag
+        "SET" => set_to_json(&row_value, options)?,
+        "CHAR" if column.set => set_to_json(&row_value, options)?,
         "CHAR" | "VARCHAR" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT" | "LONGTEXT" | "ENUM" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode() {
+            if let Some(v) = decode(&row_value, options)? {
                 JsonValue::String(v)
             } else {
                 JsonValue::Null
             }
         }
         "FLOAT" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<f32>() {
+            if let Some(v) = decode::<f32>(&row_value, options)? {
                 JsonValue::from(v)
             } else {
                 JsonValue::Null
             }
         }
         "DOUBLE" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<f64>() {
+            if let Some(v) = decode::<f64>(&row_value, options)? {
                 JsonValue::from(v)
             } else {
                 JsonValue::Null
             }
         }
+        #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+        // sent as text in both protocols, which keeps digits the decimal crates would round
+        "DECIMAL" => match ValueRef::to_owned(&row_value).try_decode_unchecked::<String>() {
+            Ok(v) if crate::options::is_decimal(&v) => options.decimal.to_json(v),
+            Ok(v) => options.decode_failed("DECIMAL", format!("malformed decimal `{v}`"))?,
+            Err(e) => options.decode_failed("DECIMAL", e)?,
+        },
         "TINYINT" | "SMALLINT" | "INT" | "MEDIUMINT" | "BIGINT" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<i64>() {
+            if let Some(v) = decode::<i64>(&row_value, options)? {
                 JsonValue::Number(v.into())
             } else {
                 JsonValue::Null
@@ -93,58 +205,430 @@ pub fn row_value_to_json(row_value: MySqlValueRef) -> Result<JsonValue, String>
         }
         "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "INT UNSIGNED" | "MEDIUMINT UNSIGNED"
         | "BIGINT UNSIGNED" | "YEAR" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<u64>() {
+            if let Some(v) = decode::<u64>(&row_value, options)? {
                 JsonValue::Number(v.into())
             } else {
                 JsonValue::Null
             }
         }
         "BOOLEAN" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode() {
+            if let Some(v) = decode(&row_value, options)? {
                 JsonValue::Bool(v)
             } else {
                 JsonValue::Null
             }
         }
-        "DATE" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<Date>() {
-                JsonValue::String(v.to_string())
-            } else {
-                JsonValue::Null
-            }
-        }
+        "DATE" => match ValueRef::to_owned(&row_value).try_decode::<Date>() {
+            Ok(v) => options.or_unrepresentable("DATE", options.datetime.date_to_json(v))?,
+            Err(e) => date_failed(&row_value, e, options)?,
+        },
         "TIME" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<Time>() {
-                JsonValue::String(v.to_string())
+            if let Some(v) = time_to_microseconds(&row_value) {
+                let v = options.mysql_time.to_json(v, &options.datetime);
+                options.or_unrepresentable("TIME", v)?
             } else {
-                JsonValue::Null
+                options.decode_failed("TIME", "malformed value")?
             }
         }
-        "DATETIME" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<PrimitiveDateTime>() {
-                JsonValue::String(v.to_string())
+        "DATETIME" => match ValueRef::to_owned(&row_value).try_decode::<PrimitiveDateTime>() {
+            Ok(v) => options.or_unrepresentable("DATETIME", options.naive_timestamp_to_json(v))?,
+            Err(e) => date_failed(&row_value, e, options)?,
+        },
+        "TIMESTAMP" => match ValueRef::to_owned(&row_value).try_decode::<OffsetDateTime>() {
+            Ok(v) => options.or_unrepresentable("TIMESTAMP", options.timestamp_to_json(v))?,
+            Err(e) => date_failed(&row_value, e, options)?,
+        },
+        "JSON" => decode(&row_value, options)?.unwrap_or_default(),
+        "BINARY" | "VARBINARY" | "TINYBLOB" | "MEDIUMBLOB" | "BLOB" | "LONGBLOB" => {
+            if let Some(v) = decode::<Vec<u8>>(&row_value, options)? {
+                options.binary.to_json(&v)
             } else {
                 JsonValue::Null
             }
         }
-        "TIMESTAMP" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<OffsetDateTime>() {
-                JsonValue::String(v.to_string())
-            } else {
-                JsonValue::Null
+        // every spatial column (POINT, LINESTRING, MULTIPOLYGON, ...) is sent as GEOMETRY
+        "GEOMETRY" => {
+            let v = ValueRef::to_owned(&row_value).try_decode_unchecked::<Vec<u8>>();
+
+            match v.map(|v| geometry_to_json(&v, options)) {
+                Ok(Some(v)) => v,
+                Ok(None) => options.decode_failed("GEOMETRY", "malformed geometry")?,
+                Err(e) => options.decode_failed("GEOMETRY", e)?,
             }
         }
-        "JSON" => ValueRef::to_owned(&row_value).try_decode().unwrap_or_default(),
-        "TINIYBLOB" | "MEDIUMBLOB" | "BLOB" | "LONGBLOB" => {
-            if let Ok(v) = ValueRef::to_owned(&row_value).try_decode::<Vec<u8>>() {
-                JsonValue::Array(v.into_iter().map(|n| JsonValue::Number(n.into())).collect())
+        "BIT" => {
+            if let Some(v) = decode::<u64>(&row_value, options)? {
+                match column.max_size {
+                    Some(1) => JsonValue::Bool(v != 0),
+                    width => options.bit.to_json(v, width),
+                }
             } else {
                 JsonValue::Null
             }
         }
         "NULL" => JsonValue::Null,
-        _ => return Err(format!("Unsupported type: {}", row_value.type_info().name())),
+        _ => {
+            let ty = row_value.type_info();
+
+            return match ValueRef::to_owned(&row_value).try_decode_unchecked::<Vec<u8>>() {
+                Ok(buf) => options.unsupported_to_json(ty.name(), Ok(&buf), fallbacks),
+                Err(e) => options.unsupported_to_json(ty.name(), Err(e.into()), fallbacks),
+            };
+        }
     };
 
     Ok(res)
-}
\ No newline at end of file
+}
+
+/// Decodes `row_value`, or returns `None` if that fails outside of [`Options::strict`] mode.
+fn decode<T>(row_value: &MySqlValueRef, options: &Options) -> Result<Option<T>, Error>
+where
+    T: for<'r> Decode<'r, MySql> + Type<MySql>,
+{
+    match ValueRef::to_owned(row_value).try_decode() {
+        Ok(v) => Ok(Some(v)),
+        Err(e) => options.decode_failed(row_value.type_info().name(), e).map(|_| None),
+    }
+}
+
+/// Decodes dates and timestamps with chrono. Returns `None` for other types.
+#[cfg(feature = "chrono")]
+fn chrono_to_json(
+    row_value: &MySqlValueRef,
+    format: &ChronoFormat,
+    options: &Options,
+) -> Option<Result<JsonValue, Error>> {
+    let v = ValueRef::to_owned(row_value);
+
+    let res = match row_value.type_info().name() {
+        "DATE" => v.try_decode().map(&*format.date),
+        "DATETIME" => v.try_decode().map(&*format.datetime),
+        "TIMESTAMP" => v.try_decode().map(&*format.datetime_utc),
+        _ => return None,
+    };
+
+    Some(res.or_else(|e| date_failed(row_value, e, options)))
+}
+
+/// DATE, DATETIME and TIMESTAMP values that failed to decode with `source`.
+///
+/// Dates that aren't valid dates, like the zero date `0000-00-00`, are handled by
+/// [`Options::invalid_date`], other failures by [`Options::strict`].
+fn date_failed(
+    row_value: &MySqlValueRef,
+    source: sqlx::Error,
+    options: &Options,
+) -> Result<JsonValue, Error> {
+    match raw_date(row_value) {
+        Some(raw) if is_invalid_date(&raw) => {
+            invalid_date_to_json(row_value, raw, options.invalid_date)
+        }
+        _ => options.decode_failed(row_value.type_info().name(), source),
+    }
+}
+
+/// An invalid date, `raw` being the way MySQL prints it.
+fn invalid_date_to_json(
+    row_value: &MySqlValueRef,
+    raw: String,
+    policy: InvalidDatePolicy,
+) -> Result<JsonValue, Error> {
+    match policy {
+        InvalidDatePolicy::Null => Ok(JsonValue::Null),
+        InvalidDatePolicy::Raw => Ok(JsonValue::String(raw)),
+        InvalidDatePolicy::Error => Err(Error::decode(
+            row_value.type_info().name(),
+            format!("invalid date `{raw}`"),
+        )),
+    }
+}
+
+/// Whether the date in `raw`, a DATE, DATETIME or TIMESTAMP as [`raw_date`] gives it, isn't a
+/// valid date. That is `false` if `raw` isn't a date at all.
+fn is_invalid_date(raw: &str) -> bool {
+    let date = raw.split(' ').next().unwrap_or(raw);
+    let fields: Vec<_> = date.split('-').map(|f| f.parse::<u16>().ok()).collect();
+
+    let [Some(year), Some(month), Some(day)] = fields[..] else {
+        return false;
+    };
+
+    let month = u8::try_from(month).ok().and_then(|m| Month::try_from(m).ok());
+
+    match (month, u8::try_from(day)) {
+        (Some(month), Ok(day)) => Date::from_calendar_date(year.into(), month, day).is_err(),
+        _ => true,
+    }
+}
+
+/// A DATE, DATETIME or TIMESTAMP the way MySQL prints it, e.g. `"0000-00-00 00:00:00"`.
+fn raw_date(row_value: &MySqlValueRef) -> Option<String> {
+    let buf = ValueRef::to_owned(row_value).try_decode_unchecked::<Vec<u8>>().ok()?;
+    format_date(buf, row_value.type_info().name() == "DATE")
+}
+
+/// Formats a date or timestamp sent in either protocol, leaving out the time with `date_only`.
+fn format_date(buf: Vec<u8>, date_only: bool) -> Option<String> {
+    match buf.split_first() {
+        // binary protocol: length, year, month, day, hours, minutes, seconds, microseconds
+        Some((&len, rest)) if usize::from(len) == rest.len() => {
+            let mut fields = [0; 11];
+            fields.get_mut(..rest.len())?.copy_from_slice(rest);
+
+            let [y0, y1, month, day, hours, minutes, seconds, us @ ..] = fields;
+            let year = u16::from_le_bytes([y0, y1]);
+            let microseconds = u32::from_le_bytes(us);
+
+            let mut s = format!("{year:04}-{month:02}-{day:02}");
+
+            if !date_only {
+                s += &format!(" {hours:02}:{minutes:02}:{seconds:02}");
+
+                if microseconds != 0 {
+                    s += &format!(".{microseconds:06}");
+                }
+            }
+
+            Some(s)
+        }
+        // text protocol
+        _ => String::from_utf8(buf).ok(),
+    }
+}
+
+/// Reads a TIME as signed microseconds.
+///
+/// sqlx decodes TIME as a time of day and panics on negative values, so it is read by hand.
+fn time_to_microseconds(row_value: &MySqlValueRef) -> Option<i64> {
+    let buf = ValueRef::to_owned(row_value).try_decode_unchecked::<Vec<u8>>().ok()?;
+    parse_time(&buf)
+}
+
+/// Parses a TIME sent in either protocol as signed microseconds.
+fn parse_time(buf: &[u8]) -> Option<i64> {
+    match buf.split_first() {
+        // binary protocol: length, sign, days, hours, minutes, seconds, microseconds
+        Some((&len, rest)) if usize::from(len) == rest.len() => {
+            let Some((head, rest)) = rest.split_first_chunk::<8>() else {
+                return (len == 0).then_some(0);
+            };
+            let [negative, d0, d1, d2, d3, hours, minutes, seconds] = *head;
+            let microseconds = match rest {
+                [] => 0,
+                [a, b, c, d] => u32::from_le_bytes([*a, *b, *c, *d]),
+                _ => return None,
+            };
+
+            let hours = i64::from(u32::from_le_bytes([d0, d1, d2, d3])) * 24 + i64::from(hours);
+            let seconds = (hours * 60 + i64::from(minutes)) * 60 + i64::from(seconds);
+            let v = seconds * 1_000_000 + i64::from(microseconds);
+
+            Some(if negative != 0 { -v } else { v })
+        }
+        // text protocol: [-]hhh:mm:ss[.ffffff]
+        _ => {
+            let s = std::str::from_utf8(buf).ok()?;
+            let (negative, s) = match s.strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s),
+            };
+
+            let mut parts = s.splitn(3, ':');
+            let hours: i64 = parts.next()?.parse().ok()?;
+            let minutes: i64 = parts.next()?.parse().ok()?;
+            let seconds = parts.next()?;
+            let (seconds, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+            let seconds: i64 = seconds.parse().ok()?;
+            let fraction: i64 = format!("{fraction:0<6}").get(..6)?.parse().ok()?;
+
+            let v = ((hours * 60 + minutes) * 60 + seconds) * 1_000_000 + fraction;
+            Some(if negative { -v } else { v })
+        }
+    }
+}
+
+/// SET values as an array of their members.
+fn set_to_json(row_value: &MySqlValueRef, options: &Options) -> Result<JsonValue, Error> {
+    match ValueRef::to_owned(row_value).try_decode_unchecked::<String>() {
+        Ok(v) => {
+            let members = v.split(',').filter(|m| !m.is_empty());
+            Ok(JsonValue::Array(members.map(|m| JsonValue::String(m.to_owned())).collect()))
+        }
+        Err(e) => options.decode_failed(row_value.type_info().name(), e),
+    }
+}
+
+/// Spatial values as GeoJSON geometries. Returns `None` if `buf` is malformed.
+fn geometry_to_json(buf: &[u8], options: &Options) -> Option<JsonValue> {
+    // MySQL prefixes plain WKB with a little-endian SRID
+    let (srid, wkb) = buf.split_first_chunk()?;
+    let srid = u32::from_le_bytes(*srid);
+    crate::geojson::from_wkb(wkb, Some(srid), options.geojson_crs)
+}
+
+/// Column details sqlx keeps private, read through the `Serialize` impl its `offline`
+/// feature adds to [`MySqlTypeInfo`].
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ColumnInfo {
+    /// Whether this is a SET column.
+    set: bool,
+    /// The `n` in `BIT(n)`.
+    max_size: Option<u32>,
+}
+
+impl ColumnInfo {
+    fn of(ty: &MySqlTypeInfo) -> Self {
+        // only needed for these, and not worth serializing every other type info for
+        if !matches!(ty.name(), "CHAR" | "BIT") {
+            return ColumnInfo::default();
+        }
+
+        let info = serde_json::to_value(ty).unwrap_or_default();
+
+        ColumnInfo {
+            // e.g. "NOT_NULL | SET"
+            set: info["flags"]
+                .as_str()
+                .is_some_and(|flags| flags.split(" | ").any(|flag| flag == "SET")),
+            max_size: info["max_size"]
+                .as_u64()
+                .and_then(|n| u32::try_from(n).ok()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::geojson::tests::{hex, POINT};
+    use crate::{DateTimeFormat, MySqlTimeFormat};
+
+    /// A binary protocol TIME: sign, days, hours, minutes, seconds and microseconds.
+    fn time_wire(negative: bool, days: u32, hms: [u8; 3], microseconds: u32) -> Vec<u8> {
+        let mut buf = vec![0, u8::from(negative)];
+        buf.extend(days.to_le_bytes());
+        buf.extend(hms);
+
+        if microseconds != 0 {
+            buf.extend(microseconds.to_le_bytes());
+        }
+
+        buf[0] = u8::try_from(buf.len() - 1).unwrap();
+        buf
+    }
+
+    #[test]
+    fn binary_time() {
+        assert_eq!(parse_time(&time_wire(false, 0, [12, 34, 56], 0)), Some(45_296_000_000));
+        // -1 day 03:15:00.5, i.e. -27:15:00.5
+        let buf = time_wire(true, 1, [3, 15, 0], 500_000);
+        assert_eq!(parse_time(&buf), Some(-98_100_500_000));
+        // 838:59:59, the largest TIME
+        let buf = time_wire(false, 34, [22, 59, 59], 0);
+        assert_eq!(parse_time(&buf), Some(3_020_399_000_000));
+        // 00:00:00 is sent with no fields at all
+        assert_eq!(parse_time(&[0]), Some(0));
+        // a length that doesn't fit
+        assert_eq!(parse_time(&[3, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn text_time() {
+        assert_eq!(parse_time(b"12:34:56"), Some(45_296_000_000));
+        assert_eq!(parse_time(b"-27:15:00.5"), Some(-98_100_500_000));
+        assert_eq!(parse_time(b"838:59:59.000001"), Some(3_020_399_000_001));
+        assert_eq!(parse_time(b"-00:00:01"), Some(-1_000_000));
+        assert_eq!(parse_time(b"00:00:00"), Some(0));
+        assert_eq!(parse_time(b"12:34"), None);
+    }
+
+    #[test]
+    fn binary_zero_dates() {
+        // zero dates and times are sent with no fields at all
+        assert_eq!(format_date(vec![0], true).as_deref(), Some("0000-00-00"));
+        assert_eq!(format_date(vec![0], false).as_deref(), Some("0000-00-00 00:00:00"));
+        // 2024-02-00, allowed without NO_ZERO_IN_DATE
+        let buf = vec![4, 0xE8, 0x07, 2, 0];
+        assert_eq!(format_date(buf, true).as_deref(), Some("2024-02-00"));
+        // 0000-00-00 12:30:00.000250
+        let buf = vec![11, 0, 0, 0, 0, 12, 30, 0, 0xFA, 0, 0, 0];
+        let raw = format_date(buf, false);
+        assert_eq!(raw.as_deref(), Some("0000-00-00 12:30:00.000250"));
+    }
+
+    #[test]
+    fn text_zero_dates() {
+        let raw = format_date(b"0000-00-00".to_vec(), true);
+        assert_eq!(raw.as_deref(), Some("0000-00-00"));
+        let raw = format_date(b"0000-00-00 00:00:00".to_vec(), false);
+        assert_eq!(raw.as_deref(), Some("0000-00-00 00:00:00"));
+    }
+
+    #[test]
+    fn invalid_dates() {
+        assert!(is_invalid_date("0000-00-00"));
+        assert!(is_invalid_date("0000-00-00 00:00:00"));
+        assert!(is_invalid_date("2024-02-00 12:30:00.000250"));
+        assert!(is_invalid_date("2023-02-29"));
+        assert!(!is_invalid_date("2024-02-29"));
+        assert!(!is_invalid_date("2024-02-29 23:59:59"));
+        // anything else failed, not the date
+        assert!(!is_invalid_date("garbage"));
+        assert!(!is_invalid_date(""));
+    }
+
+    #[test]
+    fn geometry_with_srid_prefix() {
+        let options = Options {
+            geojson_crs: true,
+            ..Default::default()
+        };
+
+        let v = geometry_to_json(&hex(&format!("E6100000{POINT}")), &options);
+        assert_eq!(
+            v,
+            Some(json!({
+                "type": "Point",
+                "coordinates": [1.0, 2.0],
+                "crs": { "type": "name", "properties": { "name": "EPSG:4326" } },
+            }))
+        );
+
+        // SRID 0 has no coordinate system to name
+        let v = geometry_to_json(&hex(&format!("00000000{POINT}")), &options).unwrap();
+        assert_eq!(v.get("crs"), None);
+
+        assert_eq!(geometry_to_json(&hex("E610"), &options), None);
+    }
+
+    #[test]
+    fn time_of_day_as_number() {
+        let seconds = |us| MySqlTimeFormat::TimeOfDay.to_json(us, &DateTimeFormat::UnixSeconds);
+        assert_eq!(seconds(82_800_000_000), Some(json!(82_800)));
+        // 25:00:00 and -27:15:00.5
+        assert_eq!(seconds(90_000_000_000), Some(json!(90_000)));
+        assert_eq!(seconds(-98_100_500_000), Some(json!(-98_100)));
+
+        let millis = |us| MySqlTimeFormat::TimeOfDay.to_json(us, &DateTimeFormat::UnixMillis);
+        assert_eq!(millis(-98_100_500_000), Some(json!(-98_100_500)));
+
+        let iso = MySqlTimeFormat::TimeOfDay.to_json(90_000_000_000, &DateTimeFormat::Iso8601);
+        assert_eq!(iso, Some(json!("25:00:00")));
+    }
+
+    #[test]
+    fn column_info() {
+        let ty = |info| serde_json::from_value::<MySqlTypeInfo>(info).unwrap();
+
+        let set = ty(json!({"type": "String", "flags": "NOT_NULL | SET", "char_set": 45}));
+        assert_eq!(ColumnInfo::of(&set), ColumnInfo { set: true, max_size: None });
+
+        let char = ty(json!({"type": "String", "flags": "NOT_NULL", "char_set": 45}));
+        assert_eq!(ColumnInfo::of(&char), ColumnInfo::default());
+
+        let bit = ty(json!({"type": "Bit", "flags": "UNSIGNED", "char_set": 63, "max_size": 5}));
+        assert_eq!(ColumnInfo::of(&bit), ColumnInfo { set: false, max_size: Some(5) });
+    }
+}
@@ -0,0 +1,1185 @@
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "chrono"))]
+use serde_json::Value as JsonValue;
+
+use std::fmt;
+use std::sync::Arc;
+
+use time::format_description::OwnedFormatItem;
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+use time::{Date, Time};
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+use crate::Error;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+
+/// Controls how column values are turned into JSON.
+///
+/// The default matches the output of `postgres::row_value_to_json` and
+/// `mysql::row_value_to_json`.
+///
+/// # Example
+/// ```
+/// use sqlx_to_json::{DecimalFormat, Options};
+///
+/// let options = Options {
+///     decimal: DecimalFormat::Number,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// How `NUMERIC` and `DECIMAL` values are emitted.
+    pub decimal: DecimalFormat,
+    /// How Postgres `UUID` values are emitted.
+    pub uuid: UuidFormat,
+    /// How Postgres `INTERVAL` values are emitted.
+    pub interval: IntervalFormat,
+    /// How Postgres geometric values (`POINT`, `POLYGON`, ...) are emitted.
+    pub geometry: GeometryFormat,
+    /// Whether GeoJSON geometries from PostGIS and MySQL spatial columns get a `crs` member
+    /// naming their SRID, if they have one, e.g.
+    /// `"crs":{"type":"name","properties":{"name":"EPSG:4326"}}`.
+    ///
+    /// RFC 7946 dropped `crs`, so this is off by default.
+    pub geojson_crs: bool,
+    /// How MySQL `BIT(n)` values wider than one bit are emitted. `BIT(1)` is always a boolean.
+    pub bit: BitFormat,
+    /// How MySQL `TIME` values, which are durations as well as times of day, are emitted.
+    pub mysql_time: MySqlTimeFormat,
+    /// What to do with MySQL `DATE`, `DATETIME` and `TIMESTAMP` values that aren't valid
+    /// dates, like the zero date `0000-00-00`.
+    pub invalid_date: InvalidDatePolicy,
+    /// How binary values (Postgres `BYTEA`, MySQL `BINARY`, `VARBINARY` and blobs) are emitted.
+    pub binary: BinaryEncoding,
+    /// How dates, times and timestamps are emitted.
+    pub datetime: DateTimeFormat,
+    /// The time zone Postgres `TIMESTAMP` and MySQL `DATETIME` values are taken to be in.
+    ///
+    /// When set, they are emitted like timestamps with a time zone, e.g.
+    /// `"2024-01-02T03:04:05+09:00"` with [`DateTimeFormat::Iso8601`]. When unset, they are
+    /// emitted without an offset.
+    pub naive_timezone: Option<TimeZone>,
+    /// The time zone timestamps with a time zone (Postgres `TIMESTAMPTZ`, MySQL `TIMESTAMP` and
+    /// those given one by [`naive_timezone`]) and Postgres `TIMETZ` values are converted to
+    /// before being emitted.
+    ///
+    /// When unset, `TIMESTAMPTZ` and MySQL `TIMESTAMP` values are emitted in UTC and `TIMETZ`
+    /// values in their own offset. As a `TIMETZ` has no date, it is converted with the offset
+    /// the zone has now, like Postgres does.
+    ///
+    /// Neither option applies to `DateTimeFormat::Chrono`, whose functions get the values as
+    /// decoded.
+    ///
+    /// [`naive_timezone`]: Options::naive_timezone
+    pub timezone: Option<TimeZone>,
+    /// Whether values that fail to decode are an [`Error::Decode`] rather than `null`.
+    ///
+    /// [`Error::Decode`]: crate::Error::Decode
+    ///
+    /// MySQL dates that aren't valid dates are still handled by [`invalid_date`].
+    ///
+    /// [`invalid_date`]: Options::invalid_date
+    pub strict: bool,
+    /// What to do with values of types that have no conversion to JSON.
+    pub unsupported_type: UnsupportedTypePolicy,
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+impl Options {
+    /// `null` for a value of type `type_name` that failed to decode, or an error in
+    /// [`Options::strict`] mode.
+    pub(crate) fn decode_failed(
+        &self,
+        type_name: &str,
+        source: impl Into<sqlx::error::BoxDynError>,
+    ) -> Result<JsonValue, Error> {
+        if self.strict {
+            Err(Error::decode(type_name, source))
+        } else {
+            Ok(JsonValue::Null)
+        }
+    }
+
+    /// Converts a value of the unsupported type `type_name` as [`Options::unsupported_type`]
+    /// says, given its raw bytes `buf`, and adds `type_name` to `fallbacks` unless that is an
+    /// error.
+    pub(crate) fn unsupported_to_json(
+        &self,
+        type_name: &str,
+        buf: Result<&[u8], sqlx::error::BoxDynError>,
+        fallbacks: &mut Vec<String>,
+    ) -> Result<JsonValue, Error> {
+        if self.unsupported_type == UnsupportedTypePolicy::Error {
+            return Err(Error::unsupported_type(type_name));
+        }
+
+        fallbacks.push(type_name.to_owned());
+
+        match (self.unsupported_type, buf) {
+            (UnsupportedTypePolicy::Error | UnsupportedTypePolicy::Null, _) => Ok(JsonValue::Null),
+            (UnsupportedTypePolicy::Raw, Ok(buf)) => Ok(self.binary.to_json(buf)),
+            (UnsupportedTypePolicy::Text, Ok(buf)) => match std::str::from_utf8(buf) {
+                Ok(text) => Ok(JsonValue::String(text.to_owned())),
+                Err(e) => self.decode_failed(type_name, e),
+            },
+            (_, Err(e)) => self.decode_failed(type_name, e),
+        }
+    }
+
+    /// `value`, or what [`Options::decode_failed`] gives if it is `None` because the configured
+    /// formats can't represent the `type_name` value it came from.
+    pub(crate) fn or_unrepresentable(
+        &self,
+        type_name: &str,
+        value: Option<JsonValue>,
+    ) -> Result<JsonValue, Error> {
+        match value {
+            Some(value) => Ok(value),
+            None => self.decode_failed(type_name, "out of range for the output format"),
+        }
+    }
+
+    /// Emits a timestamp without a time zone, in [`Options::naive_timezone`] if set.
+    ///
+    /// Returns `None` for local times the zone skips, and like
+    /// [`Options::timestamp_to_json`].
+    pub(crate) fn naive_timestamp_to_json(
+        &self,
+        timestamp: PrimitiveDateTime,
+    ) -> Option<JsonValue> {
+        let Some(zone) = self.zone(&self.naive_timezone) else {
+            return self.datetime.datetime_to_json(timestamp);
+        };
+
+        let offset = zone.local_offset(timestamp)?;
+        self.timestamp_to_json(timestamp.assume_offset(offset))
+    }
+
+    /// Emits a timestamp with a time zone, converted to [`Options::timezone`] if set.
+    ///
+    /// Returns `None` for timestamps that leave the range of dates once converted, or that
+    /// [`Options::datetime`] can't represent.
+    pub(crate) fn timestamp_to_json(&self, timestamp: OffsetDateTime) -> Option<JsonValue> {
+        let timestamp = match self.zone(&self.timezone) {
+            Some(zone) => timestamp.checked_to_offset(zone.utc_offset(timestamp))?,
+            None => timestamp,
+        };
+
+        self.datetime.offset_datetime_to_json(timestamp)
+    }
+
+    /// Emits a time of day with an offset, converted to [`Options::timezone`] if set.
+    #[cfg(feature = "postgres")]
+    pub(crate) fn time_tz_to_json(&self, time: Time, offset: UtcOffset) -> Option<JsonValue> {
+        match self.zone(&self.timezone) {
+            Some(zone) => {
+                let target = zone.utc_offset(OffsetDateTime::now_utc());
+                let shift = target.whole_seconds() - offset.whole_seconds();
+                let time = time + time::Duration::seconds(shift.into());
+                self.datetime.time_tz_to_json(time, target)
+            }
+            None => self.datetime.time_tz_to_json(time, offset),
+        }
+    }
+
+    /// `zone`, unless [`Options::datetime`] is `DateTimeFormat::Chrono`, which neither time zone
+    /// option applies to.
+    fn zone<'a>(&self, zone: &'a Option<TimeZone>) -> Option<&'a TimeZone> {
+        match self.datetime {
+            #[cfg(feature = "chrono")]
+            DateTimeFormat::Chrono(_) => None,
+            _ => zone.as_ref(),
+        }
+    }
+}
+
+/// A time zone for [`Options::naive_timezone`] and [`Options::timezone`].
+///
+/// Either a fixed offset, or rules giving the offset at any moment, so that zones with daylight
+/// saving time can be used. Crates like `time-tz` or `chrono-tz` can provide those rules.
+///
+/// # Example
+/// ```
+/// use sqlx_to_json::{Options, TimeZone};
+/// use time::{Month, UtcOffset};
+///
+/// // a crude daylight saving rule: UTC+2 from April to September, UTC+1 otherwise
+/// let offset = |month: Month| {
+///     let summer = (Month::April as u8..=Month::September as u8).contains(&(month as u8));
+///     UtcOffset::from_hms(if summer { 2 } else { 1 }, 0, 0).unwrap()
+/// };
+///
+/// let options = Options {
+///     timezone: Some(TimeZone::new(
+///         move |utc| offset(utc.month()),
+///         move |local| Some(offset(local.month())),
+///     )),
+///     naive_timezone: Some(TimeZone::fixed(UtcOffset::UTC)),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone)]
+pub struct TimeZone(TimeZoneKind);
+
+#[derive(Clone)]
+#[cfg_attr(not(any(feature = "mysql", feature = "postgres")), allow(dead_code))]
+enum TimeZoneKind {
+    Fixed(UtcOffset),
+    Rules {
+        utc_offset: Arc<dyn Fn(OffsetDateTime) -> UtcOffset + Send + Sync>,
+        local_offset: Arc<dyn Fn(PrimitiveDateTime) -> Option<UtcOffset> + Send + Sync>,
+    },
+}
+
+impl TimeZone {
+    /// A zone that is always at `offset`.
+    pub fn fixed(offset: UtcOffset) -> Self {
+        TimeZone(TimeZoneKind::Fixed(offset))
+    }
+
+    /// A zone following rules.
+    ///
+    /// `utc_offset` gives the offset of the zone at a moment in time. `local_offset` gives the
+    /// offset a local date and time in the zone has, picking one for times that occur twice
+    /// when clocks are turned back, or `None` for times skipped when they are turned forward.
+    /// Timestamps that end up without an offset are emitted as `null`.
+    pub fn new(
+        utc_offset: impl Fn(OffsetDateTime) -> UtcOffset + Send + Sync + 'static,
+        local_offset: impl Fn(PrimitiveDateTime) -> Option<UtcOffset> + Send + Sync + 'static,
+    ) -> Self {
+        TimeZone(TimeZoneKind::Rules {
+            utc_offset: Arc::new(utc_offset),
+            local_offset: Arc::new(local_offset),
+        })
+    }
+
+    /// The offset of the zone at `moment`.
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    pub(crate) fn utc_offset(&self, moment: OffsetDateTime) -> UtcOffset {
+        match &self.0 {
+            TimeZoneKind::Fixed(offset) => *offset,
+            TimeZoneKind::Rules { utc_offset, .. } => utc_offset(moment),
+        }
+    }
+
+    /// The offset of the local date and time `local` in the zone, if it exists.
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    pub(crate) fn local_offset(&self, local: PrimitiveDateTime) -> Option<UtcOffset> {
+        match &self.0 {
+            TimeZoneKind::Fixed(offset) => Some(*offset),
+            TimeZoneKind::Rules { local_offset, .. } => local_offset(local),
+        }
+    }
+}
+
+impl From<UtcOffset> for TimeZone {
+    fn from(offset: UtcOffset) -> Self {
+        TimeZone::fixed(offset)
+    }
+}
+
+impl fmt::Debug for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            TimeZoneKind::Fixed(offset) => f.debug_tuple("TimeZone").field(offset).finish(),
+            TimeZoneKind::Rules { .. } => f.debug_struct("TimeZone").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Output format for Postgres `NUMERIC` and MySQL `DECIMAL` values.
+///
+/// Decoding these columns requires either the `bigdecimal` or the `rust_decimal` feature.
+/// Values keep every digit the database sends, whichever of the two is enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecimalFormat {
+    /// A JSON string holding the exact decimal, e.g. `"1234.5600"`.
+    #[default]
+    String,
+    /// A JSON number.
+    ///
+    /// This is only lossless with the `arbitrary_precision` feature, which enables the
+    /// feature of the same name on `serde_json`. Without it the value is rounded to an `f64`.
+    Number,
+}
+
+#[cfg(all(
+    any(feature = "bigdecimal", feature = "rust_decimal"),
+    any(feature = "mysql", feature = "postgres")
+))]
+impl DecimalFormat {
+    pub(crate) fn to_json(self, decimal: String) -> JsonValue {
+        match self {
+            DecimalFormat::String => JsonValue::String(decimal),
+            DecimalFormat::Number => match decimal.parse() {
+                Ok(n) => JsonValue::Number(n),
+                Err(_) => JsonValue::String(decimal),
+            },
+        }
+    }
+}
+
+/// Whether `s` is a finite decimal the way the databases print one, e.g. `-1234.5600`.
+#[cfg(all(
+    any(feature = "bigdecimal", feature = "rust_decimal"),
+    any(feature = "mysql", feature = "postgres")
+))]
+pub(crate) fn is_decimal(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let (int, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+
+    !int.is_empty()
+        && !fraction.is_empty()
+        && int
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+}
+
+/// Output format for Postgres `UUID` values.
+///
+/// Decoding `UUID` columns requires the `uuid` feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UuidFormat {
+    /// `"67e55044-10b1-426f-9247-bb680e5fe0c8"`
+    #[default]
+    Hyphenated,
+    /// `"67e5504410b1426f9247bb680e5fe0c8"`
+    Simple,
+    /// `"urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"`
+    Urn,
+}
+
+#[cfg(all(feature = "uuid", feature = "postgres"))]
+impl UuidFormat {
+    pub(crate) fn to_json(self, uuid: sqlx::types::Uuid) -> JsonValue {
+        let s = match self {
+            UuidFormat::Hyphenated => uuid.hyphenated().to_string(),
+            UuidFormat::Simple => uuid.simple().to_string(),
+            UuidFormat::Urn => uuid.urn().to_string(),
+        };
+
+        JsonValue::String(s)
+    }
+}
+
+/// Output format for Postgres `INTERVAL` values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntervalFormat {
+    /// An ISO 8601 duration, e.g. `"P1Y2M3DT4H5M6.5S"`.
+    ///
+    /// Like Postgres' `iso_8601` interval style, each component carries its own sign,
+    /// e.g. `"P-1DT2H"`.
+    #[default]
+    Iso8601,
+    /// The fields Postgres stores, e.g. `{"months":14,"days":3,"microseconds":14706500000}`.
+    Object,
+}
+
+#[cfg(feature = "postgres")]
+impl IntervalFormat {
+    pub(crate) fn to_json(self, months: i32, days: i32, microseconds: i64) -> JsonValue {
+        match self {
+            IntervalFormat::Iso8601 => {
+                JsonValue::String(iso8601_duration(months, days, microseconds))
+            }
+            IntervalFormat::Object => serde_json::json!({
+                "months": months,
+                "days": days,
+                "microseconds": microseconds,
+            }),
+        }
+    }
+}
+
+/// Output format for the Postgres geometric types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GeometryFormat {
+    /// Plain objects mirroring the Postgres types:
+    ///
+    /// | type      | JSON                                    |
+    /// |-----------|-----------------------------------------|
+    /// | `POINT`   | `{"x":1.0,"y":2.0}`                     |
+    /// | `LINE`    | `{"a":1.0,"b":-1.0,"c":0.0}`            |
+    /// | `LSEG`    | `[{"x":..,"y":..},{"x":..,"y":..}]`     |
+    /// | `BOX`     | `{"high":{"x":..,"y":..},"low":{..}}`   |
+    /// | `PATH`    | `{"closed":true,"points":[{..},..]}`    |
+    /// | `POLYGON` | `[{"x":..,"y":..},..]`                  |
+    /// | `CIRCLE`  | `{"center":{"x":..,"y":..},"radius":..}`|
+    #[default]
+    Native,
+    /// [GeoJSON] geometry objects: `POINT` becomes a `Point`, `LSEG` and `PATH` a `LineString`,
+    /// and `BOX` and `POLYGON` a `Polygon`.
+    ///
+    /// `LINE` and `CIRCLE` have no GeoJSON equivalent and keep their [`Native`] form.
+    ///
+    /// [GeoJSON]: https://datatracker.ietf.org/doc/html/rfc7946
+    /// [`Native`]: GeometryFormat::Native
+    GeoJson,
+}
+
+/// Output format for MySQL `BIT(n)` values with `n > 1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BitFormat {
+    /// A JSON number, e.g. `5` for `b'101'`.
+    #[default]
+    Integer,
+    /// A string of `n` binary digits, e.g. `"00101"` for `b'101'` in a `BIT(5)` column.
+    BitString,
+}
+
+#[cfg(feature = "mysql")]
+impl BitFormat {
+    pub(crate) fn to_json(self, bits: u64, width: Option<u32>) -> JsonValue {
+        match (self, width) {
+            (BitFormat::BitString, Some(width)) => {
+                JsonValue::String(format!("{bits:0width$b}", width = width as usize))
+            }
+            _ => JsonValue::Number(bits.into()),
+        }
+    }
+}
+
+/// Output format for MySQL `TIME` values, which range from `-838:59:59` to `838:59:59`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MySqlTimeFormat {
+    /// Values within a day are emitted as times of day, following [`Options::datetime`].
+    /// Negative values and those of a day or more are emitted as with [`Clock`], except with
+    /// [`DateTimeFormat::UnixSeconds`] and [`DateTimeFormat::UnixMillis`], which count on past a
+    /// day and below zero so that all values of a column are numbers.
+    ///
+    /// [`Clock`]: MySqlTimeFormat::Clock
+    #[default]
+    TimeOfDay,
+    /// A signed duration the way MySQL prints it, e.g. `"-27:15:00"` or `"01:02:03.5"`.
+    Clock,
+    /// An ISO 8601 duration in hours, minutes and seconds, signed like
+    /// [`IntervalFormat::Iso8601`], e.g. `"PT-27H-15M"`.
+    Iso8601,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlTimeFormat {
+    /// Returns `None` if `datetime` can't represent the time of day, see
+    /// [`DateTimeFormat::time_to_json`].
+    pub(crate) fn to_json(self, microseconds: i64, datetime: &DateTimeFormat) -> Option<JsonValue> {
+        const DAY: i64 = 86_400_000_000;
+
+        match (self, datetime) {
+            // truncated toward zero, like the seconds of a time of day
+            (MySqlTimeFormat::TimeOfDay, DateTimeFormat::UnixSeconds) => {
+                Some((microseconds / 1_000_000).into())
+            }
+            (MySqlTimeFormat::TimeOfDay, DateTimeFormat::UnixMillis) => {
+                Some((microseconds / 1_000).into())
+            }
+            (MySqlTimeFormat::TimeOfDay, _) if (0..DAY).contains(&microseconds) => {
+                datetime.time_to_json(Time::MIDNIGHT + time::Duration::microseconds(microseconds))
+            }
+            (MySqlTimeFormat::TimeOfDay | MySqlTimeFormat::Clock, _) => {
+                Some(JsonValue::String(clock_duration(microseconds)))
+            }
+            (MySqlTimeFormat::Iso8601, _) => {
+                Some(JsonValue::String(iso8601_duration(0, 0, microseconds)))
+            }
+        }
+    }
+}
+
+/// `[-]hh:mm:ss`, with as many fractional digits as needed and hours going past 24.
+#[cfg(feature = "mysql")]
+fn clock_duration(microseconds: i64) -> String {
+    let sign = if microseconds < 0 { "-" } else { "" };
+    let microseconds = microseconds.unsigned_abs();
+
+    let hours = microseconds / 3_600_000_000;
+    let minutes = microseconds / 60_000_000 % 60;
+    let seconds = microseconds / 1_000_000 % 60;
+    let fraction = microseconds % 1_000_000;
+
+    if fraction == 0 {
+        format!("{sign}{hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        let fraction = format!("{fraction:06}");
+        let fraction = fraction.trim_end_matches('0');
+        format!("{sign}{hours:02}:{minutes:02}:{seconds:02}.{fraction}")
+    }
+}
+
+/// Handling of MySQL dates that can't be decoded, like `0000-00-00`, `0000-00-00 00:00:00` or
+/// `2024-02-00`, which MySQL allows depending on its SQL mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvalidDatePolicy {
+    /// Emit `null`.
+    #[default]
+    Null,
+    /// Emit the value the way MySQL prints it, e.g. `"0000-00-00"`.
+    Raw,
+    /// Fail the conversion. `mysql::rows_to_json` names the offending column in the error.
+    Error,
+}
+
+/// Handling of values whose SQL type has no conversion to JSON, e.g. Postgres `TSVECTOR` or
+/// types from extensions.
+///
+/// `postgres::rows_to_json_with_fallbacks` and `mysql::rows_to_json_with_fallbacks` report the
+/// columns that needed a fallback.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnsupportedTypePolicy {
+    /// Fail the conversion with an [`Error::UnsupportedType`].
+    ///
+    /// [`Error::UnsupportedType`]: crate::Error::UnsupportedType
+    #[default]
+    Error,
+    /// Emit `null`.
+    Null,
+    /// Emit the bytes the database sent, encoded as [`Options::binary`] says.
+    ///
+    /// These are in the binary wire format of the type for Postgres and in its text format for
+    /// MySQL.
+    Raw,
+    /// Emit the bytes the database sent as a string, or `null` if they aren't valid UTF-8.
+    ///
+    /// This works for types that are sent as text, like Postgres `CITEXT` or anything in a
+    /// text-format result.
+    Text,
+}
+
+/// A column that had values converted by an [`UnsupportedTypePolicy`] fallback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackColumn {
+    /// Name of the column.
+    pub column: String,
+    /// Position of the column in the row, starting at 0.
+    pub index: usize,
+    /// Name of the unsupported SQL type. For arrays, ranges and composites this is the type of
+    /// the element or field that needed the fallback.
+    pub type_name: String,
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+impl FallbackColumn {
+    /// Adds the column at `index` to `columns`, unless it is there already, if `fallbacks` has
+    /// the types a fallback converted while converting one of its values.
+    pub(crate) fn record(
+        columns: &mut Vec<FallbackColumn>,
+        column: &str,
+        index: usize,
+        fallbacks: Vec<String>,
+    ) {
+        let Some(type_name) = fallbacks.into_iter().next() else {
+            return;
+        };
+
+        if !columns.iter().any(|c| c.index == index) {
+            columns.push(FallbackColumn {
+                column: column.to_owned(),
+                index,
+                type_name,
+            });
+        }
+    }
+}
+
+/// Output format for binary values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BinaryEncoding {
+    /// A JSON array of byte values, e.g. `[222,173,190,239]`.
+    #[default]
+    Array,
+    /// Standard, padded base64, e.g. `"3q2+7w=="`.
+    Base64,
+    /// URL-safe base64 without padding, e.g. `"3q2-7w"`.
+    Base64UrlSafe,
+    /// Lowercase hex, e.g. `"deadbeef"`.
+    Hex,
+    /// Lowercase hex with a `\x` prefix, as Postgres prints `BYTEA`, e.g. `"\\xdeadbeef"`.
+    PostgresHex,
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+impl BinaryEncoding {
+    pub(crate) fn to_json(self, bytes: &[u8]) -> JsonValue {
+        use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+        use base64::Engine;
+
+        match self {
+            BinaryEncoding::Array => JsonValue::Array(
+                bytes
+                    .iter()
+                    .map(|n| JsonValue::Number((*n).into()))
+                    .collect(),
+            ),
+            BinaryEncoding::Base64 => JsonValue::String(STANDARD.encode(bytes)),
+            BinaryEncoding::Base64UrlSafe => JsonValue::String(URL_SAFE_NO_PAD.encode(bytes)),
+            BinaryEncoding::Hex => JsonValue::String(hex(bytes)),
+            BinaryEncoding::PostgresHex => JsonValue::String(format!("\\x{}", hex(bytes))),
+        }
+    }
+}
+
+/// Output format for dates, times and timestamps.
+///
+/// # Example
+/// ```
+/// use sqlx_to_json::{DateTimeFormat, Options};
+/// use time::format_description;
+///
+/// let options = Options {
+///     datetime: DateTimeFormat::Custom(
+///         format_description::parse_owned::<2>("[day]/[month]/[year]").unwrap(),
+///     ),
+///     ..Default::default()
+/// };
+/// ```
+///
+/// Non-exhaustive, as the `chrono` feature adds a variant.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub enum DateTimeFormat {
+    /// The [`Display`] output of the `time` types, e.g. `"2024-01-02 3:04:05.0 +00:00:00"`.
+    ///
+    /// [`Display`]: std::fmt::Display
+    #[default]
+    Display,
+    /// ISO 8601, e.g. `"2024-01-02"`, `"03:04:05.5"`, `"2024-01-02T03:04:05"`.
+    ///
+    /// Timestamps with a time zone are RFC 3339, e.g. `"2024-01-02T03:04:05Z"` or
+    /// `"2024-01-02T03:04:05+09:00"`.
+    Iso8601,
+    /// Seconds since the Unix epoch as a JSON number, e.g. `1704164645`.
+    ///
+    /// Timestamps without a time zone are taken to be in [`Options::naive_timezone`], and dates
+    /// (at midnight) as well as timestamps without one when it is unset to be UTC. Times of day
+    /// become the seconds since midnight, in UTC for `TIMETZ`. Fractions of a second are
+    /// dropped.
+    UnixSeconds,
+    /// Milliseconds since the Unix epoch, like [`UnixSeconds`].
+    ///
+    /// [`UnixSeconds`]: DateTimeFormat::UnixSeconds
+    UnixMillis,
+    /// A `time` [format description], used for every date, time and timestamp.
+    ///
+    /// Values the description asks for more than they have, like the offset of a timestamp
+    /// without a time zone, are emitted as with [`Iso8601`] instead.
+    ///
+    /// A `TIMETZ` is formatted as a timestamp on an arbitrary date, so that the description can
+    /// include its offset.
+    ///
+    /// [format description]: time::format_description
+    /// [`Iso8601`]: DateTimeFormat::Iso8601
+    Custom(OwnedFormatItem),
+    /// Dates, times and timestamps are decoded with [`chrono`] and passed to these functions.
+    #[cfg(feature = "chrono")]
+    Chrono(ChronoFormat),
+}
+
+// These return `None` for values the format can't represent, like dates outside of chrono's
+// range.
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+impl DateTimeFormat {
+    pub(crate) fn date_to_json(&self, date: Date) -> Option<JsonValue> {
+        Some(match self {
+            DateTimeFormat::Display | DateTimeFormat::Iso8601 => {
+                JsonValue::String(date.to_string())
+            }
+            DateTimeFormat::UnixSeconds | DateTimeFormat::UnixMillis => {
+                return self.offset_datetime_to_json(date.midnight().assume_utc());
+            }
+            DateTimeFormat::Custom(format) => custom(date.format(format), || date.to_string()),
+            #[cfg(feature = "chrono")]
+            DateTimeFormat::Chrono(format) => (format.date)(chrono_date(date)?),
+        })
+    }
+
+    pub(crate) fn time_to_json(&self, time: Time) -> Option<JsonValue> {
+        let since_midnight = time - Time::MIDNIGHT;
+
+        Some(match self {
+            DateTimeFormat::Display => JsonValue::String(time.to_string()),
+            DateTimeFormat::Iso8601 => JsonValue::String(iso8601_time(time)),
+            DateTimeFormat::UnixSeconds => since_midnight.whole_seconds().into(),
+            DateTimeFormat::UnixMillis => (since_midnight.whole_milliseconds() as i64).into(),
+            DateTimeFormat::Custom(format) => custom(time.format(format), || iso8601_time(time)),
+            #[cfg(feature = "chrono")]
+            DateTimeFormat::Chrono(format) => (format.time)(chrono_time(time)?),
+        })
+    }
+
+    #[cfg(feature = "postgres")]
+    pub(crate) fn time_tz_to_json(&self, time: Time, offset: UtcOffset) -> Option<JsonValue> {
+        let iso8601 = || format!("{}{}", iso8601_time(time), iso8601_offset(offset));
+        // the time of day in UTC, wrapping around midnight
+        let since_midnight =
+            time - time::Duration::seconds(offset.whole_seconds().into()) - Time::MIDNIGHT;
+
+        Some(match self {
+            DateTimeFormat::Display => JsonValue::String(format!("{time} {offset}")),
+            DateTimeFormat::Iso8601 => JsonValue::String(iso8601()),
+            DateTimeFormat::UnixSeconds => since_midnight.whole_seconds().into(),
+            DateTimeFormat::UnixMillis => (since_midnight.whole_milliseconds() as i64).into(),
+            DateTimeFormat::Custom(format) => {
+                let datetime = PrimitiveDateTime::new(Date::MIN, time).assume_offset(offset);
+                custom(datetime.format(format), iso8601)
+            }
+            #[cfg(feature = "chrono")]
+            DateTimeFormat::Chrono(format) => {
+                let offset = FixedOffset::east_opt(offset.whole_seconds())?;
+                (format.time_tz)(chrono_time(time)?, offset)
+            }
+        })
+    }
+
+    pub(crate) fn datetime_to_json(&self, datetime: PrimitiveDateTime) -> Option<JsonValue> {
+        let iso8601 = || format!("{}T{}", datetime.date(), iso8601_time(datetime.time()));
+
+        Some(match self {
+            DateTimeFormat::Display => JsonValue::String(datetime.to_string()),
+            DateTimeFormat::Iso8601 => JsonValue::String(iso8601()),
+            DateTimeFormat::UnixSeconds | DateTimeFormat::UnixMillis => {
+                return self.offset_datetime_to_json(datetime.assume_utc());
+            }
+            DateTimeFormat::Custom(format) => custom(datetime.format(format), iso8601),
+            #[cfg(feature = "chrono")]
+            DateTimeFormat::Chrono(format) => (format.datetime)(chrono_datetime(datetime)?),
+        })
+    }
+
+    pub(crate) fn offset_datetime_to_json(&self, datetime: OffsetDateTime) -> Option<JsonValue> {
+        let iso8601 = || {
+            format!(
+                "{}T{}{}",
+                datetime.date(),
+                iso8601_time(datetime.time()),
+                iso8601_offset(datetime.offset()),
+            )
+        };
+
+        Some(match self {
+            DateTimeFormat::Display => JsonValue::String(datetime.to_string()),
+            DateTimeFormat::Iso8601 => JsonValue::String(iso8601()),
+            DateTimeFormat::UnixSeconds => datetime.unix_timestamp().into(),
+            DateTimeFormat::UnixMillis => {
+                let millis = datetime.unix_timestamp_nanos().div_euclid(1_000_000);
+                (millis as i64).into()
+            }
+            DateTimeFormat::Custom(format) => custom(datetime.format(format), iso8601),
+            #[cfg(feature = "chrono")]
+            DateTimeFormat::Chrono(format) => {
+                let utc = datetime.checked_to_offset(UtcOffset::UTC)?;
+                let utc = chrono_datetime(PrimitiveDateTime::new(utc.date(), utc.time()))?;
+                (format.datetime_utc)(utc.and_utc())
+            }
+        })
+    }
+}
+
+/// Functions turning [`chrono`] dates, times and timestamps into JSON, for
+/// [`DateTimeFormat::Chrono`].
+///
+/// The default emits the same strings as [`DateTimeFormat::Iso8601`]. The functions can
+/// capture runtime settings, like a time zone or pattern read from a config file.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use serde_json::Value as JsonValue;
+/// use chrono::{DateTime, FixedOffset, Utc};
+/// use sqlx_to_json::{ChronoFormat, DateTimeFormat, Options};
+///
+/// let zone = FixedOffset::east_opt(9 * 3600).unwrap();
+/// let pattern = String::from("%d %b %Y %H:%M");
+///
+/// let options = Options {
+///     datetime: DateTimeFormat::Chrono(ChronoFormat {
+///         datetime_utc: Arc::new(move |v: DateTime<Utc>| {
+///             JsonValue::String(v.with_timezone(&zone).format(&pattern).to_string())
+///         }),
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+/// ```
+#[cfg(feature = "chrono")]
+#[derive(Clone)]
+pub struct ChronoFormat {
+    /// For `DATE` values.
+    pub date: Arc<dyn Fn(NaiveDate) -> JsonValue + Send + Sync>,
+    /// For `TIME` values, including MySQL ones emitted as [`MySqlTimeFormat::TimeOfDay`].
+    pub time: Arc<dyn Fn(NaiveTime) -> JsonValue + Send + Sync>,
+    /// For Postgres `TIMESTAMP` and MySQL `DATETIME` values.
+    pub datetime: Arc<dyn Fn(NaiveDateTime) -> JsonValue + Send + Sync>,
+    /// For Postgres `TIMESTAMPTZ` and MySQL `TIMESTAMP` values.
+    pub datetime_utc: Arc<dyn Fn(DateTime<Utc>) -> JsonValue + Send + Sync>,
+    /// For Postgres `TIMETZ` values.
+    pub time_tz: Arc<dyn Fn(NaiveTime, FixedOffset) -> JsonValue + Send + Sync>,
+}
+
+#[cfg(feature = "chrono")]
+impl Default for ChronoFormat {
+    fn default() -> Self {
+        ChronoFormat {
+            date: Arc::new(|v| JsonValue::String(v.to_string())),
+            time: Arc::new(|v| JsonValue::String(v.format("%H:%M:%S%.f").to_string())),
+            datetime: Arc::new(|v| JsonValue::String(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string())),
+            datetime_utc: Arc::new(|v| {
+                JsonValue::String(v.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }),
+            time_tz: Arc::new(|v, offset| {
+                let time = v.format("%H:%M:%S%.f");
+
+                if offset.local_minus_utc() == 0 {
+                    JsonValue::String(format!("{time}Z"))
+                } else {
+                    JsonValue::String(format!("{time}{offset}"))
+                }
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl fmt::Debug for ChronoFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChronoFormat").finish_non_exhaustive()
+    }
+}
+
+// `time` values reach `DateTimeFormat::Chrono` only where they are decoded by hand, from nested
+// Postgres values
+
+#[cfg(all(feature = "chrono", any(feature = "mysql", feature = "postgres")))]
+fn chrono_date(date: Date) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(
+        date.year(),
+        u8::from(date.month()).into(),
+        date.day().into(),
+    )
+}
+
+#[cfg(all(feature = "chrono", any(feature = "mysql", feature = "postgres")))]
+fn chrono_time(time: Time) -> Option<NaiveTime> {
+    let (h, m, s, nanos) = time.as_hms_nano();
+    NaiveTime::from_hms_nano_opt(h.into(), m.into(), s.into(), nanos)
+}
+
+#[cfg(all(feature = "chrono", any(feature = "mysql", feature = "postgres")))]
+fn chrono_datetime(datetime: PrimitiveDateTime) -> Option<NaiveDateTime> {
+    Some(chrono_date(datetime.date())?.and_time(chrono_time(datetime.time())?))
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+fn custom(
+    formatted: Result<String, time::error::Format>,
+    fallback: impl FnOnce() -> String,
+) -> JsonValue {
+    JsonValue::String(formatted.unwrap_or_else(|_| fallback()))
+}
+
+/// `hh:mm:ss`, followed by as many fractional digits as needed.
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+fn iso8601_time(time: Time) -> String {
+    let (h, m, s, nanos) = time.as_hms_nano();
+
+    if nanos == 0 {
+        format!("{h:02}:{m:02}:{s:02}")
+    } else {
+        let fraction = format!("{nanos:09}");
+        format!("{h:02}:{m:02}:{s:02}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
+/// `Z` for UTC, `±hh:mm` otherwise, with `:ss` added if the offset has seconds.
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+fn iso8601_offset(offset: UtcOffset) -> String {
+    if offset.is_utc() {
+        return "Z".to_owned();
+    }
+
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    let (h, m, s) = offset.as_hms();
+    let (h, m, s) = (h.unsigned_abs(), m.unsigned_abs(), s.unsigned_abs());
+
+    if s == 0 {
+        format!("{sign}{h:02}:{m:02}")
+    } else {
+        format!("{sign}{h:02}:{m:02}:{s:02}")
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+fn hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    let mut s = String::with_capacity(bytes.len() * 2);
+
+    for b in bytes {
+        let _ = write!(s, "{b:02x}");
+    }
+
+    s
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+fn iso8601_duration(months: i32, days: i32, microseconds: i64) -> String {
+    use std::fmt::Write;
+
+    let mut s = String::from("P");
+
+    for (n, unit) in [(months / 12, 'Y'), (months % 12, 'M'), (days, 'D')] {
+        if n != 0 {
+            let _ = write!(s, "{n}{unit}");
+        }
+    }
+
+    let hours = microseconds / 3_600_000_000;
+    let minutes = microseconds / 60_000_000 % 60;
+    let seconds = microseconds % 60_000_000;
+
+    if hours != 0 || minutes != 0 || seconds != 0 {
+        s.push('T');
+
+        for (n, unit) in [(hours, 'H'), (minutes, 'M')] {
+            if n != 0 {
+                let _ = write!(s, "{n}{unit}");
+            }
+        }
+
+        if seconds != 0 {
+            let sign = if seconds < 0 { "-" } else { "" };
+            let (whole, fraction) = (seconds.abs() / 1_000_000, seconds.abs() % 1_000_000);
+
+            if fraction == 0 {
+                let _ = write!(s, "{sign}{whole}S");
+            } else {
+                let fraction = format!("{fraction:06}");
+                let _ = write!(s, "{sign}{whole}.{}S", fraction.trim_end_matches('0'));
+            }
+        }
+    }
+
+    if s == "P" {
+        s.push_str("T0S");
+    }
+
+    s
+}
+
+#[cfg(all(test, any(feature = "mysql", feature = "postgres")))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+    fn decimals() {
+        assert!(is_decimal("0"));
+        assert!(is_decimal("-1234.5600"));
+        // 65 digits, the most a MySQL DECIMAL holds
+        assert!(is_decimal(&format!(
+            "{}.{}",
+            "9".repeat(35),
+            "9".repeat(30)
+        )));
+        assert!(!is_decimal(""));
+        assert!(!is_decimal("-"));
+        assert!(!is_decimal("1."));
+        assert!(!is_decimal(".5"));
+        assert!(!is_decimal("1e5"));
+        assert!(!is_decimal("NaN"));
+        assert!(!is_decimal("-Infinity"));
+    }
+
+    #[test]
+    fn iso8601_durations() {
+        let cases = [
+            ((0, 0, 0), "PT0S"),
+            ((14, 3, 3_723_500_000), "P1Y2M3DT1H2M3.5S"),
+            // every component carries its own sign, the way Postgres prints intervals
+            ((-14, 0, 0), "P-1Y-2M"),
+            ((1, -1, 0), "P1M-1D"),
+            ((0, 0, -98_100_000_000), "PT-27H-15M"),
+            ((0, 0, -500_000), "PT-0.5S"),
+            ((0, 0, 1), "PT0.000001S"),
+        ];
+
+        for ((months, days, microseconds), expected) in cases {
+            assert_eq!(iso8601_duration(months, days, microseconds), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "mysql")]
+    fn clock_durations() {
+        let cases = [
+            (0, "00:00:00"),
+            (3_723_000_001, "01:02:03.000001"),
+            (-98_100_500_000, "-27:15:00.5"),
+            (-500_000, "-00:00:00.5"),
+            // the largest MySQL TIME
+            (3_020_399_000_000, "838:59:59"),
+        ];
+
+        for (microseconds, expected) in cases {
+            assert_eq!(clock_duration(microseconds), expected);
+        }
+    }
+
+    #[test]
+    fn binary_encodings() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef];
+        let cases = [
+            (BinaryEncoding::Array, json!([222, 173, 190, 239])),
+            (BinaryEncoding::Base64, json!("3q2+7w==")),
+            (BinaryEncoding::Base64UrlSafe, json!("3q2-7w")),
+            (BinaryEncoding::Hex, json!("deadbeef")),
+            (BinaryEncoding::PostgresHex, json!("\\xdeadbeef")),
+        ];
+
+        for (encoding, expected) in cases {
+            assert_eq!(encoding.to_json(&bytes), expected, "{encoding:?}");
+        }
+    }
+
+    fn datetime(
+        (year, month, day): (i32, u8, u8),
+        (h, m, s, us): (u8, u8, u8, u32),
+    ) -> PrimitiveDateTime {
+        let month = time::Month::try_from(month).unwrap();
+        Date::from_calendar_date(year, month, day)
+            .unwrap()
+            .with_hms_micro(h, m, s, us)
+            .unwrap()
+    }
+
+    #[test]
+    fn iso8601_datetimes() {
+        let format = DateTimeFormat::Iso8601;
+        let v = datetime((2024, 1, 2), (3, 4, 5, 0));
+        assert_eq!(
+            format.datetime_to_json(v),
+            Some(json!("2024-01-02T03:04:05"))
+        );
+        assert_eq!(format.date_to_json(v.date()), Some(json!("2024-01-02")));
+
+        let v = datetime((2024, 1, 2), (3, 4, 5, 120_000));
+        assert_eq!(format.time_to_json(v.time()), Some(json!("03:04:05.12")));
+        assert_eq!(
+            format.offset_datetime_to_json(v.assume_utc()),
+            Some(json!("2024-01-02T03:04:05.12Z"))
+        );
+
+        let offset = UtcOffset::from_hms(-9, -30, 0).unwrap();
+        assert_eq!(
+            format.offset_datetime_to_json(v.assume_offset(offset)),
+            Some(json!("2024-01-02T03:04:05.12-09:30"))
+        );
+    }
+
+    #[test]
+    fn unix_datetimes() {
+        let v = datetime((2024, 1, 2), (3, 4, 5, 678_000));
+        let seconds = DateTimeFormat::UnixSeconds.datetime_to_json(v);
+        assert_eq!(seconds, Some(json!(1_704_164_645)));
+        let millis = DateTimeFormat::UnixMillis.datetime_to_json(v);
+        assert_eq!(millis, Some(json!(1_704_164_645_678_i64)));
+
+        // rounded down before the epoch, not toward it
+        let v = datetime((1969, 12, 31), (23, 59, 59, 999_500)).assume_utc();
+        let seconds = DateTimeFormat::UnixSeconds.offset_datetime_to_json(v);
+        assert_eq!(seconds, Some(json!(-1)));
+        let millis = DateTimeFormat::UnixMillis.offset_datetime_to_json(v);
+        assert_eq!(millis, Some(json!(-1)));
+
+        let v = datetime((1969, 12, 31), (0, 0, 0, 0)).date();
+        let days = DateTimeFormat::UnixSeconds.date_to_json(v);
+        assert_eq!(days, Some(json!(-86_400)));
+    }
+
+    #[test]
+    fn custom_datetimes() {
+        let format = |description| {
+            DateTimeFormat::Custom(time::format_description::parse_owned::<2>(description).unwrap())
+        };
+        let v = datetime((2024, 1, 2), (3, 4, 5, 0));
+
+        let day_first = format("[day]/[month]/[year] [hour]:[minute]");
+        assert_eq!(
+            day_first.datetime_to_json(v),
+            Some(json!("02/01/2024 03:04"))
+        );
+
+        // a value without an offset falls back to ISO 8601
+        let with_offset = format("[year]-[month]-[day] [offset_hour]");
+        assert_eq!(
+            with_offset.datetime_to_json(v),
+            Some(json!("2024-01-02T03:04:05"))
+        );
+        assert_eq!(with_offset.time_to_json(v.time()), Some(json!("03:04:05")));
+        assert_eq!(
+            with_offset.offset_datetime_to_json(v.assume_utc()),
+            Some(json!("2024-01-02 00"))
+        );
+    }
+
+    #[test]
+    fn fallback_columns() {
+        let mut columns = vec![];
+
+        FallbackColumn::record(&mut columns, "a", 0, vec![]);
+        assert!(columns.is_empty());
+
+        // only the first type of a value, and only the first value of a column, is kept
+        let types = vec!["ltree".to_owned(), "tsvector".to_owned()];
+        FallbackColumn::record(&mut columns, "b", 1, types);
+        FallbackColumn::record(&mut columns, "b", 1, vec!["citext".to_owned()]);
+        FallbackColumn::record(&mut columns, "c", 2, vec!["citext".to_owned()]);
+
+        let names: Vec<_> = columns
+            .iter()
+            .map(|c| (c.column.as_str(), c.index, c.type_name.as_str()))
+            .collect();
+        assert_eq!(names, [("b", 1, "ltree"), ("c", 2, "citext")]);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_ignores_time_zones() {
+        let zone = TimeZone::fixed(UtcOffset::from_hms(9, 0, 0).unwrap());
+        let options = Options {
+            datetime: DateTimeFormat::Chrono(ChronoFormat::default()),
+            naive_timezone: Some(zone.clone()),
+            timezone: Some(zone),
+            ..Default::default()
+        };
+
+        let timestamp = time::Date::from_calendar_date(2024, time::Month::January, 2)
+            .unwrap()
+            .with_hms(3, 4, 5)
+            .unwrap();
+        assert_eq!(
+            options.naive_timestamp_to_json(timestamp),
+            options.datetime.datetime_to_json(timestamp)
+        );
+
+        let utc = timestamp.assume_utc();
+        assert_eq!(
+            options.timestamp_to_json(utc),
+            options.datetime.offset_datetime_to_json(utc)
+        );
+
+        #[cfg(feature = "postgres")]
+        {
+            let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+            assert_eq!(
+                options.time_tz_to_json(timestamp.time(), offset),
+                options.datetime.time_tz_to_json(timestamp.time(), offset)
+            );
+        }
+    }
+}
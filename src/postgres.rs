@@ -1,11 +1,33 @@
 use std::collections::HashMap;
+use std::ops::Bound;
 
 use serde_json::Value as JsonValue;
 use sqlx::{
-    postgres::{PgRow, PgValueRef}, Column, Row, TypeInfo, Value, ValueRef
+    error::BoxDynError,
+    postgres::{
+        types::{PgInterval, PgRange, PgRecordDecoder, PgTimeTz},
+        PgHasArrayType, PgRow, PgTypeInfo, PgTypeKind, PgValue, PgValueFormat, PgValueRef,
+    },
+    Column, Decode, Postgres, Row, Type, TypeInfo, Value, ValueRef,
 };
 use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 
+#[cfg(feature = "ipnetwork")]
+use sqlx::types::ipnetwork::IpNetwork;
+#[cfg(feature = "mac_address")]
+use sqlx::types::mac_address::MacAddress;
+#[cfg(feature = "uuid")]
+use sqlx::types::Uuid;
+
+#[cfg(feature = "chrono")]
+use chrono::{FixedOffset, NaiveTime};
+
+#[cfg(feature = "chrono")]
+use crate::{ChronoFormat, DateTimeFormat};
+use crate::{Error, FallbackColumn, Options};
+
+mod binary;
+
 /// A wrapper for [`row_value_to_json`] function.
 /// 
 /// # Example
@@ -17,26 +39,86 @@ use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 /// let output = sqlx_to_json::postgres::rows_to_json(rows).unwrap();
 /// ```
 /// [`row_value_to_json`]: fn.row_value_to_json.html
-pub fn rows_to_json(rows: Vec<PgRow>) -> Result<Vec<HashMap<String, JsonValue>>, String> {
-    if rows.is_empty() {
-        return Ok(vec![]);
-    }
+pub fn rows_to_json(rows: Vec<PgRow>) -> Result<Vec<HashMap<String, JsonValue>>, Error> {
+    rows_to_json_with_options(rows, &Options::default())
+}
+
+/// Same as [`rows_to_json`] but with custom [`Options`].
+///
+/// # Example
+/// ```no_run
+/// # async fn example(mut conn: sqlx::PgConnection) {
+/// use sqlx_to_json::{DecimalFormat, Options};
+///
+/// let options = Options {
+///     decimal: DecimalFormat::Number,
+///     ..Default::default()
+/// };
+///
+/// let rows = sqlx::query("SELECT * FROM orders LIMIT 10").fetch_all(&mut conn).await.unwrap();
+/// let output = sqlx_to_json::postgres::rows_to_json_with_options(rows, &options).unwrap();
+/// # }
+/// ```
+pub fn rows_to_json_with_options(
+    rows: Vec<PgRow>,
+    options: &Options,
+) -> Result<Vec<HashMap<String, JsonValue>>, Error> {
+    rows_to_json_with_fallbacks(rows, options).map(|(output, _)| output)
+}
 
+/// Same as [`rows_to_json_with_options`], but also returns the columns that had values
+/// converted by the [`Options::unsupported_type`] fallback, in column order.
+///
+/// # Example
+/// ```no_run
+/// # async fn example(mut conn: sqlx::PgConnection) {
+/// use sqlx_to_json::{Options, UnsupportedTypePolicy};
+///
+/// let options = Options {
+///     unsupported_type: UnsupportedTypePolicy::Text,
+///     ..Default::default()
+/// };
+///
+/// let rows = sqlx::query("SELECT * FROM orders LIMIT 10").fetch_all(&mut conn).await.unwrap();
+/// let (output, fallbacks) =
+///     sqlx_to_json::postgres::rows_to_json_with_fallbacks(rows, &options).unwrap();
+///
+/// for fallback in fallbacks {
+///     eprintln!("column `{}` has unsupported type {}", fallback.column, fallback.type_name);
+/// }
+/// # }
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn rows_to_json_with_fallbacks(
+    rows: Vec<PgRow>,
+    options: &Options,
+) -> Result<(Vec<HashMap<String, JsonValue>>, Vec<FallbackColumn>), Error> {
     let mut output = Vec::with_capacity(rows.len());
+    let mut fallback_columns: Vec<FallbackColumn> = vec![];
 
     for row in rows {
         let mut map = HashMap::new();
 
         for (i, column) in row.columns().iter().enumerate() {
-            let row_value = row.try_get_raw(i).map_err(|e| e.to_string())?;
-            let value_json = row_value_to_json(row_value).map_err(|e| e.to_string())?;
+            let row_value = row.try_get_raw(i).map_err(|source| Error::Column {
+                column: column.name().to_owned(),
+                index: i,
+                source,
+            })?;
+            let mut fallbacks = vec![];
+            let value_json = value_to_json(row_value, options, &mut fallbacks)
+                .map_err(|e| e.in_column(column.name(), i))?;
+            FallbackColumn::record(&mut fallback_columns, column.name(), i, fallbacks);
+
             map.insert(column.name().to_string(), value_json);
         }
 
         output.push(map);
     }
 
-    Ok(output)
+    fallback_columns.sort_by_key(|c| c.index);
+
+    Ok((output, fallback_columns))
 }
 
 /// # Example
@@ -59,100 +141,604 @@ pub fn rows_to_json(rows: Vec<PgRow>) -> Result<Vec<HashMap<String, JsonValue>>,
 ///     output.push(map);
 /// }
 /// ```
-pub fn row_value_to_json(v: PgValueRef) -> Result<JsonValue, String> {
+pub fn row_value_to_json(v: PgValueRef) -> Result<JsonValue, Error> {
+    row_value_to_json_with_options(v, &Options::default())
+}
+
+/// Same as [`row_value_to_json`] but with custom [`Options`].
+pub fn row_value_to_json_with_options(
+    v: PgValueRef,
+    options: &Options,
+) -> Result<JsonValue, Error> {
+    value_to_json(v, options, &mut vec![])
+}
+
+/// [`row_value_to_json_with_options`], adding the types of the values converted by an
+/// [`UnsupportedTypePolicy`] fallback to `fallbacks`.
+///
+/// [`UnsupportedTypePolicy`]: crate::UnsupportedTypePolicy
+fn value_to_json(
+    v: PgValueRef,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
     if v.is_null() {
         return Ok(JsonValue::Null);
     }
 
-    let res = match v.type_info().name() {
+    // Domains are converted as their base type. As `v` still carries the domain type, values
+    // are decoded unchecked; the match on the type name already guarantees compatibility.
+    let ty = base_type(&v.type_info());
+
+    if let PgTypeKind::Domain(_) = v.type_info().kind() {
+        if let Some(res) = domain_to_json(&v, &ty, options, fallbacks) {
+            return res;
+        }
+    }
+
+    match ty.kind() {
+        PgTypeKind::Array(element) => return array_to_json(&v, element, options, fallbacks),
+        PgTypeKind::Range(_) => return range_to_json(&v, options, fallbacks),
+        PgTypeKind::Enum(_) => return enum_to_json(&v, options),
+        PgTypeKind::Composite(fields) => {
+            return record_to_json(&v, Some(fields), options, fallbacks)
+        }
+        _ => {}
+    }
+
+    #[cfg(feature = "chrono")]
+    if let DateTimeFormat::Chrono(format) = &options.datetime {
+        if let Some(res) = chrono_to_json(&v, ty.name(), format, options) {
+            return res;
+        }
+    }
+
+    let res = match ty.name() {
         "CHAR" | "VARCHAR" | "TEXT" | "NAME" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode() {
+            if let Some(v) = decode(&v, options)? {
                 JsonValue::String(v)
             } else {
                 JsonValue::Null
             }
         }
         "FLOAT4" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<f32>() {
+            if let Some(v) = decode::<f32>(&v, options)? {
                 JsonValue::from(v)
             } else {
                 JsonValue::Null
             }
         }
         "FLOAT8" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<f64>() {
+            if let Some(v) = decode::<f64>(&v, options)? {
                 JsonValue::from(v)
             } else {
                 JsonValue::Null
             }
         }
         "INT2" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<i16>() {
+            if let Some(v) = decode::<i16>(&v, options)? {
                 JsonValue::Number(v.into())
             } else {
                 JsonValue::Null
             }
         }
         "INT4" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<i32>() {
+            if let Some(v) = decode::<i32>(&v, options)? {
                 JsonValue::Number(v.into())
             } else {
                 JsonValue::Null
             }
         }
         "INT8" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<i64>() {
+            if let Some(v) = decode::<i64>(&v, options)? {
                 JsonValue::Number(v.into())
             } else {
                 JsonValue::Null
             }
         }
+        // sqlx's decimals lose the display scale, e.g. `1.5000` for a `NUMERIC(10,1)`, and
+        // `rust_decimal` rounds past 28 digits, so values are read directly
+        #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+        "NUMERIC" => match v.format() {
+            PgValueFormat::Binary => wire_to_json(&v, "NUMERIC", options, fallbacks)?,
+            PgValueFormat::Text => match v.as_str() {
+                Ok(s) if crate::options::is_decimal(s) => options.decimal.to_json(s.to_owned()),
+                // NaN and the infinities, as in the binary format
+                Ok(_) => options.decode_failed("NUMERIC", "not a finite number")?,
+                Err(e) => options.decode_failed("NUMERIC", e)?,
+            },
+        },
+        #[cfg(feature = "uuid")]
+        "UUID" => {
+            if let Some(v) = decode::<Uuid>(&v, options)? {
+                options.uuid.to_json(v)
+            } else {
+                JsonValue::Null
+            }
+        }
+        #[cfg(feature = "ipnetwork")]
+        "INET" | "CIDR" => {
+            if let Some(v) = decode::<IpNetwork>(&v, options)? {
+                JsonValue::String(v.to_string())
+            } else {
+                JsonValue::Null
+            }
+        }
+        #[cfg(feature = "mac_address")]
+        "MACADDR" => {
+            if let Some(v) = decode::<MacAddress>(&v, options)? {
+                mac_address_json(v.bytes())
+            } else {
+                JsonValue::Null
+            }
+        }
         "BOOL" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode() {
+            if let Some(v) = decode(&v, options)? {
                 JsonValue::Bool(v)
             } else {
                 JsonValue::Null
             }
         }
         "DATE" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<Date>() {
-                JsonValue::String(v.to_string())
+            if let Some(v) = decode::<Date>(&v, options)? {
+                options.or_unrepresentable("DATE", options.datetime.date_to_json(v))?
             } else {
                 JsonValue::Null
             }
         }
         "TIME" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<Time>() {
-                JsonValue::String(v.to_string())
+            if let Some(v) = decode::<Time>(&v, options)? {
+                options.or_unrepresentable("TIME", options.datetime.time_to_json(v))?
+            } else {
+                JsonValue::Null
+            }
+        }
+        "TIMETZ" => {
+            if let Some(PgTimeTz { time, offset }) = decode(&v, options)? {
+                options.or_unrepresentable("TIMETZ", options.time_tz_to_json(time, offset))?
             } else {
                 JsonValue::Null
             }
         }
         "TIMESTAMP" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<PrimitiveDateTime>() {
-                JsonValue::String(v.to_string())
+            if let Some(v) = decode::<PrimitiveDateTime>(&v, options)? {
+                options.or_unrepresentable("TIMESTAMP", options.naive_timestamp_to_json(v))?
             } else {
                 JsonValue::Null
             }
         }
         "TIMESTAMPTZ" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<OffsetDateTime>() {
-                JsonValue::String(v.to_string())
+            if let Some(v) = decode::<OffsetDateTime>(&v, options)? {
+                options.or_unrepresentable("TIMESTAMPTZ", options.timestamp_to_json(v))?
             } else {
                 JsonValue::Null
             }
         }
-        "JSON" | "JSONB" => ValueRef::to_owned(&v).try_decode().unwrap_or_default(),
+        "INTERVAL" => {
+            if let Some(v) = decode::<PgInterval>(&v, options)? {
+                options.interval.to_json(v.months, v.days, v.microseconds)
+            } else {
+                JsonValue::Null
+            }
+        }
+        "JSON" | "JSONB" => decode(&v, options)?.unwrap_or_default(),
         "BYTEA" => {
-            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<Vec<u8>>() {
-                JsonValue::Array(v.into_iter().map(|n| JsonValue::Number(n.into())).collect())
+            if let Some(v) = decode::<Vec<u8>>(&v, options)? {
+                options.binary.to_json(&v)
             } else {
                 JsonValue::Null
             }
         }
+        "POINT" | "LINE" | "LSEG" | "BOX" | "PATH" | "POLYGON" | "CIRCLE" => {
+            wire_to_json(&v, ty.name(), options, fallbacks)?
+        }
+        // PostGIS sends EWKB
+        #[cfg(feature = "postgis")]
+        "geometry" | "geography" => wire_to_json(&v, ty.name(), options, fallbacks)?,
+        name if multirange_bound(name).is_some() => {
+            multirange_to_json(&v, &ty, options, fallbacks)?
+        }
+        "RECORD" => record_to_json(&v, None, options, fallbacks)?,
         "VOID" => JsonValue::Null,
-        _ => return Err(format!("Unsupported type: {}", v.type_info().name())),
+        _ => return options.unsupported_to_json(v.type_info().name(), v.as_bytes(), fallbacks),
     };
 
     Ok(res)
 }
+
+/// Arrays of any supported type, as nested JSON arrays for multi-dimensional ones.
+fn array_to_json(
+    v: &PgValueRef,
+    element: &PgTypeInfo,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
+    // sqlx only decodes one-dimensional arrays with a lower bound of 1
+    if let (PgValueFormat::Binary, Ok(buf)) = (v.format(), v.as_bytes()) {
+        let mut element_to_json =
+            |buf: &[u8]| wire_value_to_json(element, buf, options, fallbacks);
+
+        return match binary::array_to_json(buf, &mut element_to_json)? {
+            Some(v) => Ok(v),
+            None => options.decode_failed(v.type_info().name(), "malformed array"),
+        };
+    }
+
+    let Some(elements) = decode::<Vec<Nested>>(v, options)? else {
+        return Ok(JsonValue::Null);
+    };
+
+    elements
+        .iter()
+        .map(|e| value_to_json(e.0.as_ref(), options, fallbacks))
+        .collect::<Result<_, _>>()
+        .map(JsonValue::Array)
+}
+
+/// Resolves domains, including domains over domains, to their base type.
+fn base_type(ty: &PgTypeInfo) -> PgTypeInfo {
+    match ty.kind() {
+        PgTypeKind::Domain(base) => base_type(base),
+        _ => ty.clone(),
+    }
+}
+
+/// The type name [`binary::to_json`] knows `ty` by.
+fn binary_type_name(ty: &PgTypeInfo) -> &str {
+    match ty.kind() {
+        PgTypeKind::Domain(base) => binary_type_name(base),
+        // enum labels are sent as plain text
+        PgTypeKind::Enum(_) => "TEXT",
+        _ => ty.name(),
+    }
+}
+
+/// User-defined enums as their label.
+fn enum_to_json(v: &PgValueRef, options: &Options) -> Result<JsonValue, Error> {
+    // `String` only declares itself compatible with the built-in text types, which is why
+    // `decode` doesn't check types
+    Ok(decode(v, options)?.map_or(JsonValue::Null, JsonValue::String))
+}
+
+/// Ranges as `{"lower":..,"upper":..,"lower_inclusive":..,"upper_inclusive":..,"empty":..}`.
+fn range_to_json(
+    v: &PgValueRef,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
+    // PgRange decodes `empty` the same as an unbounded range
+    let empty = match v.format() {
+        PgValueFormat::Binary => v.as_bytes().is_ok_and(binary::is_empty_range),
+        PgValueFormat::Text => v.as_str().is_ok_and(|v| v == "empty"),
+    };
+
+    if empty {
+        return Ok(range_json(Bound::Unbounded, Bound::Unbounded, true));
+    }
+
+    let Some(range) = decode::<PgRange<Nested>>(v, options)? else {
+        return Ok(JsonValue::Null);
+    };
+
+    let mut bound_to_json = |bound| -> Result<_, Error> {
+        Ok(match bound {
+            Bound::Included(Nested(v)) => {
+                Bound::Included(value_to_json(v.as_ref(), options, fallbacks)?)
+            }
+            Bound::Excluded(Nested(v)) => {
+                Bound::Excluded(value_to_json(v.as_ref(), options, fallbacks)?)
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        })
+    };
+
+    Ok(range_json(
+        bound_to_json(range.start)?,
+        bound_to_json(range.end)?,
+        false,
+    ))
+}
+
+/// Domains over JSONB, arrays, ranges and composites, whose decoders in sqlx look at the type
+/// of the value itself and so reject the domain, or lose the type of custom array elements.
+/// They are read from the binary wire format instead.
+///
+/// `ty` is the base type of the domain. Returns `None` for domains over other types.
+fn domain_to_json(
+    v: &PgValueRef,
+    ty: &PgTypeInfo,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Option<Result<JsonValue, Error>> {
+    let checked = matches!(ty.kind(), PgTypeKind::Range(_) | PgTypeKind::Composite(_));
+    // text arrays fall back to sqlx, which at least handles built-in element types
+    let wire = checked || ty.name() == "JSONB" || matches!(ty.kind(), PgTypeKind::Array(_));
+
+    match (v.format(), v.as_bytes()) {
+        (PgValueFormat::Binary, Ok(buf)) if wire => {
+            Some(wire_value_to_json(ty, buf, options, fallbacks))
+        }
+        // text JSONB has no version byte for sqlx to mishandle
+        _ if checked => {
+            let res = options.decode_failed(v.type_info().name(), "not in the binary format");
+            Some(res)
+        }
+        _ => None,
+    }
+}
+
+/// Converts a value of type `ty` from the binary wire format, for values sqlx can't decode.
+fn wire_value_to_json(
+    ty: &PgTypeInfo,
+    buf: &[u8],
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
+    let res = match ty.kind() {
+        PgTypeKind::Domain(base) => return wire_value_to_json(base, buf, options, fallbacks),
+        PgTypeKind::Array(element) => {
+            let mut element_to_json =
+                |buf: &[u8]| wire_value_to_json(element, buf, options, fallbacks);
+            binary::array_to_json(buf, &mut element_to_json)?
+        }
+        PgTypeKind::Range(bound) => {
+            binary::range_to_json(binary_type_name(bound), buf, options, fallbacks)?
+        }
+        PgTypeKind::Composite(fields) => wire_record_to_json(fields, buf, options, fallbacks)?,
+        _ => match multirange_bound(ty.name()) {
+            Some(bound) => binary::multirange_to_json(bound, buf, options, fallbacks)?,
+            None => return binary::to_json(binary_type_name(ty), buf, options, fallbacks),
+        },
+    };
+
+    match res {
+        Some(res) => Ok(res),
+        None => options.decode_failed(ty.name(), "malformed value"),
+    }
+}
+
+/// Composite values in the binary wire format, see [`record_to_json`].
+///
+/// Returns `Ok(None)` if `buf` is malformed.
+fn wire_record_to_json(
+    fields: &[(String, PgTypeInfo)],
+    buf: &[u8],
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<Option<JsonValue>, Error> {
+    let Some(values) = binary::record_fields(buf).filter(|v| v.len() == fields.len()) else {
+        return Ok(None);
+    };
+
+    let mut object = serde_json::Map::with_capacity(fields.len());
+
+    for ((name, ty), value) in fields.iter().zip(values) {
+        let value = match value {
+            Some(buf) => wire_value_to_json(ty, buf, options, fallbacks)?,
+            None => JsonValue::Null,
+        };
+
+        object.insert(name.clone(), value);
+    }
+
+    Ok(Some(JsonValue::Object(object)))
+}
+
+/// Types sqlx has no decoder for, read straight from the binary wire format.
+fn wire_to_json(
+    v: &PgValueRef,
+    ty: &str,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
+    match (v.format(), v.as_bytes()) {
+        (PgValueFormat::Binary, Ok(buf)) => binary::to_json(ty, buf, options, fallbacks),
+        _ => options.decode_failed(v.type_info().name(), "not in the binary format"),
+    }
+}
+
+/// Multiranges (Postgres 14+) as an array of range objects, see [`range_to_json`].
+///
+/// `ty` is the multirange type, or the base type of a domain over one.
+fn multirange_to_json(
+    v: &PgValueRef,
+    ty: &PgTypeInfo,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
+    match (v.format(), v.as_bytes()) {
+        (PgValueFormat::Binary, Ok(buf)) => wire_value_to_json(ty, buf, options, fallbacks),
+        _ => options.decode_failed(v.type_info().name(), "not in the binary format"),
+    }
+}
+
+/// The name of the type of the range bounds of the built-in multirange type named `ty`.
+fn multirange_bound(ty: &str) -> Option<&'static str> {
+    Some(match ty {
+        "int4multirange" => "INT4",
+        "int8multirange" => "INT8",
+        "nummultirange" => "NUMERIC",
+        "tsmultirange" => "TIMESTAMP",
+        "tstzmultirange" => "TIMESTAMPTZ",
+        "datemultirange" => "DATE",
+        _ => return None,
+    })
+}
+
+/// Decodes dates, times and timestamps with chrono. Returns `None` for other types.
+#[cfg(feature = "chrono")]
+fn chrono_to_json(
+    v: &PgValueRef,
+    ty: &str,
+    format: &ChronoFormat,
+    options: &Options,
+) -> Option<Result<JsonValue, Error>> {
+    let v = ValueRef::to_owned(v);
+
+    let res = match ty {
+        "DATE" => v.try_decode_unchecked().map(&*format.date),
+        "TIME" => v.try_decode_unchecked().map(&*format.time),
+        "TIMESTAMP" => v.try_decode_unchecked().map(&*format.datetime),
+        "TIMESTAMPTZ" => v.try_decode_unchecked().map(&*format.datetime_utc),
+        "TIMETZ" => v
+            .try_decode_unchecked::<PgTimeTz<NaiveTime, FixedOffset>>()
+            .map(|v| (format.time_tz)(v.time, v.offset)),
+        _ => return None,
+    };
+
+    Some(res.or_else(|e| options.decode_failed(v.type_info().name(), e)))
+}
+
+/// `"08:00:2b:01:02:03"`, the way Postgres prints a `MACADDR`.
+#[cfg(feature = "mac_address")]
+fn mac_address_json(bytes: [u8; 6]) -> JsonValue {
+    let octets: Vec<_> = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    JsonValue::String(octets.join(":"))
+}
+
+fn range_json(lower: Bound<JsonValue>, upper: Bound<JsonValue>, empty: bool) -> JsonValue {
+    let split = |bound| match bound {
+        Bound::Included(v) => (v, true),
+        Bound::Excluded(v) => (v, false),
+        Bound::Unbounded => (JsonValue::Null, false),
+    };
+
+    let (lower, lower_inclusive) = split(lower);
+    let (upper, upper_inclusive) = split(upper);
+
+    serde_json::json!({
+        "lower": lower,
+        "upper": upper,
+        "lower_inclusive": lower_inclusive,
+        "upper_inclusive": upper_inclusive,
+        "empty": empty,
+    })
+}
+
+/// Composite types as an object keyed by field name.
+///
+/// Anonymous records, e.g. from `SELECT ROW(a, b)`, carry no field names (`fields` is `None`)
+/// and become an array instead.
+fn record_to_json(
+    v: &PgValueRef,
+    fields: Option<&[(String, PgTypeInfo)]>,
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
+    let count = match (v.format(), v.as_bytes()) {
+        (PgValueFormat::Binary, Ok(buf)) => buf.first_chunk().copied().map(u32::from_be_bytes),
+        _ => None,
+    };
+
+    let Some(count) = count else {
+        return options.decode_failed(v.type_info().name(), "not in the binary format");
+    };
+    let mut decoder = match PgRecordDecoder::new(v.clone()) {
+        Ok(decoder) => decoder,
+        Err(e) => return options.decode_failed(v.type_info().name(), e),
+    };
+
+    let mut values = Vec::new();
+
+    for _ in 0..count {
+        let Nested(field) = match decoder.try_decode() {
+            Ok(field) => field,
+            Err(e) => return options.decode_failed(v.type_info().name(), e),
+        };
+
+        values.push(value_to_json(field.as_ref(), options, fallbacks)?);
+    }
+
+    Ok(match fields {
+        Some(fields) => {
+            let names = fields.iter().map(|(name, _)| name.clone());
+            JsonValue::Object(names.zip(values).collect())
+        }
+        None => JsonValue::Array(values),
+    })
+}
+
+/// Decodes `v` without checking its type, or returns `None` if that fails outside of
+/// [`Options::strict`] mode.
+fn decode<T>(v: &PgValueRef, options: &Options) -> Result<Option<T>, Error>
+where
+    T: for<'r> Decode<'r, Postgres>,
+{
+    match ValueRef::to_owned(v).try_decode_unchecked() {
+        Ok(v) => Ok(Some(v)),
+        Err(e) => options.decode_failed(v.type_info().name(), e).map(|_| None),
+    }
+}
+
+/// A value nested inside another one: an array element, range bound or record field.
+///
+/// It is kept undecoded so it can go back through [`value_to_json`].
+struct Nested(PgValue);
+
+impl Type<Postgres> for Nested {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("unknown")
+    }
+
+    fn compatible(_: &PgTypeInfo) -> bool {
+        true
+    }
+}
+
+impl PgHasArrayType for Nested {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_unknown")
+    }
+
+    fn array_compatible(_: &PgTypeInfo) -> bool {
+        true
+    }
+}
+
+impl Decode<'_, Postgres> for Nested {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(Nested(ValueRef::to_owned(&value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn two_dimensional_range_array() {
+        // '{{"[1,10)"}}'::INT4RANGE[][]
+        let buf = [
+            &2i32.to_be_bytes()[..],
+            &0i32.to_be_bytes(),
+            &3904u32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &17i32.to_be_bytes(),
+            // the lower bound is inclusive
+            &[0x02],
+            &4i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &10i32.to_be_bytes(),
+        ]
+        .concat();
+
+        let ty = <PgRange<i32> as PgHasArrayType>::array_type_info();
+        let v = wire_value_to_json(&ty, &buf, &Options::default(), &mut vec![]).unwrap();
+        assert_eq!(
+            v,
+            json!([[{
+                "lower": 1,
+                "upper": 10,
+                "lower_inclusive": true,
+                "upper_inclusive": false,
+                "empty": false,
+            }]])
+        );
+    }
+}
@@ -0,0 +1,674 @@
+//! Conversion straight from the Postgres binary wire format.
+//!
+//! sqlx can only hand out a [`PgValueRef`] for values it sliced out of a row itself, which
+//! leaves the elements of multi-dimensional arrays and the ranges inside a multirange out of
+//! reach. These are decoded here from their raw bytes instead, for the built-in scalar types.
+//!
+//! The same goes for types sqlx has no decoder for at all, like the geometric types.
+//!
+//! [`PgValueRef`]: sqlx::postgres::PgValueRef
+
+use std::ops::Bound;
+
+use serde_json::Value as JsonValue;
+use time::{Date, Duration, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::{Error, Options};
+
+mod geometric;
+
+/// Converts a single non-null value of the type named `ty`.
+///
+/// The types converted by an [`UnsupportedTypePolicy`] fallback are added to `fallbacks`.
+///
+/// [`UnsupportedTypePolicy`]: crate::UnsupportedTypePolicy
+pub(super) fn to_json(
+    ty: &str,
+    buf: &[u8],
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<JsonValue, Error> {
+    let res = match ty {
+        "CHAR" | "VARCHAR" | "TEXT" | "NAME" => std::str::from_utf8(buf)
+            .ok()
+            .map(|v| JsonValue::String(v.to_owned())),
+        "FLOAT4" => exact(buf).map(f32::from_be_bytes).map(JsonValue::from),
+        "FLOAT8" => exact(buf).map(f64::from_be_bytes).map(JsonValue::from),
+        "INT2" => exact(buf).map(i16::from_be_bytes).map(JsonValue::from),
+        "INT4" => exact(buf).map(i32::from_be_bytes).map(JsonValue::from),
+        "INT8" => exact(buf).map(i64::from_be_bytes).map(JsonValue::from),
+        #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+        "NUMERIC" => numeric(buf).map(|v| options.decimal.to_json(v)),
+        #[cfg(feature = "uuid")]
+        "UUID" => sqlx::types::Uuid::from_slice(buf)
+            .ok()
+            .map(|v| options.uuid.to_json(v)),
+        #[cfg(feature = "ipnetwork")]
+        "INET" | "CIDR" => inet(buf).map(|v| JsonValue::String(v.to_string())),
+        #[cfg(feature = "mac_address")]
+        "MACADDR" => exact(buf).map(super::mac_address_json),
+        "BOOL" => exact(buf).map(|[v]: [u8; 1]| JsonValue::Bool(v != 0)),
+        "DATE" => exact(buf)
+            .map(i32::from_be_bytes)
+            .and_then(|days| epoch().date().checked_add(Duration::days(days.into())))
+            .and_then(|v| options.datetime.date_to_json(v)),
+        "TIME" => exact(buf)
+            .map(i64::from_be_bytes)
+            .map(|us| Time::MIDNIGHT + Duration::microseconds(us))
+            .and_then(|v| options.datetime.time_to_json(v)),
+        "TIMETZ" => time_tz(buf).and_then(|(time, offset)| options.time_tz_to_json(time, offset)),
+        "TIMESTAMP" => timestamp(buf).and_then(|v| options.naive_timestamp_to_json(v)),
+        "TIMESTAMPTZ" => timestamp(buf).and_then(|v| options.timestamp_to_json(v.assume_utc())),
+        "INTERVAL" => interval(buf).map(|(months, days, microseconds)| {
+            options.interval.to_json(months, days, microseconds)
+        }),
+        "JSON" => serde_json::from_slice(buf).ok(),
+        "JSONB" => match buf.split_first() {
+            Some((1, json)) => serde_json::from_slice(json).ok(),
+            _ => None,
+        },
+        "POINT" | "LINE" | "LSEG" | "BOX" | "PATH" | "POLYGON" | "CIRCLE" => {
+            geometric::to_json(ty, buf, options.geometry)
+        }
+        #[cfg(feature = "postgis")]
+        "geometry" | "geography" => crate::geojson::from_wkb(buf, None, options.geojson_crs),
+        "BYTEA" => Some(options.binary.to_json(buf)),
+        _ => return options.unsupported_to_json(ty, Ok(buf), fallbacks),
+    };
+
+    match res {
+        Some(res) => Ok(res),
+        None => options.decode_failed(ty, "malformed value"),
+    }
+}
+
+/// Converts an array of any dimension and any lower bounds into nested JSON arrays.
+///
+/// `element_to_json` converts each non-null element. Returns `Ok(None)` if `buf` is malformed.
+pub(super) fn array_to_json<F>(
+    mut buf: &[u8],
+    element_to_json: &mut F,
+) -> Result<Option<JsonValue>, Error>
+where
+    F: FnMut(&[u8]) -> Result<JsonValue, Error>,
+{
+    let Some(dims) = array_dims(&mut buf) else {
+        return Ok(None);
+    };
+
+    if dims.is_empty() {
+        return Ok(Some(JsonValue::Array(vec![])));
+    }
+
+    array_dim_to_json(&dims, &mut buf, element_to_json)
+}
+
+/// Reads the array header, returning the length of each dimension.
+fn array_dims(buf: &mut &[u8]) -> Option<Vec<usize>> {
+    let ndim = i32::from_be_bytes(take(buf)?);
+    // flags, then the element OID, which is already known from the type info
+    let _flags = i32::from_be_bytes(take(buf)?);
+    let _element = u32::from_be_bytes(take(buf)?);
+
+    (0..ndim)
+        .map(|_| {
+            let len = i32::from_be_bytes(take(buf)?);
+            let _lower = i32::from_be_bytes(take(buf)?);
+            usize::try_from(len).ok()
+        })
+        .collect()
+}
+
+/// Reads the elements of the outermost dimension in `dims`, recursing into the inner ones.
+///
+/// Returns `Ok(None)` if `buf` ends early.
+fn array_dim_to_json<F>(
+    dims: &[usize],
+    buf: &mut &[u8],
+    element_to_json: &mut F,
+) -> Result<Option<JsonValue>, Error>
+where
+    F: FnMut(&[u8]) -> Result<JsonValue, Error>,
+{
+    let Some((&len, inner)) = dims.split_first() else {
+        return Ok(None);
+    };
+
+    let mut items = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let item = if inner.is_empty() {
+            let Some(len) = take(buf).map(i32::from_be_bytes) else {
+                return Ok(None);
+            };
+
+            // a length of -1 marks a NULL element
+            match usize::try_from(len) {
+                Ok(len) if len <= buf.len() => {
+                    let (value, rest) = buf.split_at(len);
+                    *buf = rest;
+                    element_to_json(value)?
+                }
+                Ok(_) => return Ok(None),
+                Err(_) => JsonValue::Null,
+            }
+        } else {
+            match array_dim_to_json(inner, buf, element_to_json)? {
+                Some(v) => v,
+                None => return Ok(None),
+            }
+        };
+
+        items.push(item);
+    }
+
+    Ok(Some(JsonValue::Array(items)))
+}
+
+/// Converts a multirange into an array of range objects.
+///
+/// `bound` is the name of the type of the range bounds. Returns `Ok(None)` if `buf` is
+/// malformed.
+pub(super) fn multirange_to_json(
+    bound: &str,
+    mut buf: &[u8],
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<Option<JsonValue>, Error> {
+    let Some(count) = take(&mut buf).map(u32::from_be_bytes) else {
+        return Ok(None);
+    };
+
+    let mut ranges = Vec::new();
+
+    for _ in 0..count {
+        let Some(range) = value(&mut buf) else {
+            return Ok(None);
+        };
+
+        match range_to_json(bound, range, options, fallbacks)? {
+            Some(range) => ranges.push(range),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(JsonValue::Array(ranges)))
+}
+
+/// Splits a composite value into its fields, `None` standing for `NULL` ones.
+///
+/// Returns `None` if `buf` is malformed.
+pub(super) fn record_fields(mut buf: &[u8]) -> Option<Vec<Option<&[u8]>>> {
+    let count = u32::from_be_bytes(take(&mut buf)?);
+
+    let fields = (0..count)
+        .map(|_| {
+            // the field type OID, which is already known from the type info
+            let _oid = u32::from_be_bytes(take(&mut buf)?);
+
+            // a length of -1 marks a NULL field
+            let len = i32::from_be_bytes(take(&mut buf)?);
+            let Ok(len) = usize::try_from(len) else {
+                return Some(None);
+            };
+
+            let (value, rest) = buf.split_at_checked(len)?;
+            buf = rest;
+            Some(Some(value))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    buf.is_empty().then_some(fields)
+}
+
+const RANGE_EMPTY: u8 = 0x01;
+const RANGE_LB_INC: u8 = 0x02;
+const RANGE_UB_INC: u8 = 0x04;
+const RANGE_LB_INF: u8 = 0x08;
+const RANGE_UB_INF: u8 = 0x10;
+
+/// Whether the binary range in `buf` is `empty`.
+pub(super) fn is_empty_range(buf: &[u8]) -> bool {
+    buf.first().is_some_and(|flags| flags & RANGE_EMPTY != 0)
+}
+
+/// Converts a range into a range object.
+///
+/// `bound` is the name of the type of the range bounds. Returns `Ok(None)` if `buf` ends early.
+pub(super) fn range_to_json(
+    bound: &str,
+    mut buf: &[u8],
+    options: &Options,
+    fallbacks: &mut Vec<String>,
+) -> Result<Option<JsonValue>, Error> {
+    let buf = &mut buf;
+    let Some([flags]) = take(buf) else {
+        return Ok(None);
+    };
+
+    if flags & RANGE_EMPTY != 0 {
+        return Ok(Some(super::range_json(
+            Bound::Unbounded,
+            Bound::Unbounded,
+            true,
+        )));
+    }
+
+    let mut bound_to_json = |infinite, inclusive| -> Result<Option<Bound<JsonValue>>, Error> {
+        if flags & infinite != 0 {
+            return Ok(Some(Bound::Unbounded));
+        }
+
+        let Some(value) = value(buf) else {
+            return Ok(None);
+        };
+        let value = to_json(bound, value, options, fallbacks)?;
+
+        Ok(Some(if flags & inclusive != 0 {
+            Bound::Included(value)
+        } else {
+            Bound::Excluded(value)
+        }))
+    };
+
+    let Some(lower) = bound_to_json(RANGE_LB_INF, RANGE_LB_INC)? else {
+        return Ok(None);
+    };
+    let Some(upper) = bound_to_json(RANGE_UB_INF, RANGE_UB_INC)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(super::range_json(lower, upper, false)))
+}
+
+/// Midnight on 2000-01-01, which Postgres counts dates and timestamps from.
+fn epoch() -> PrimitiveDateTime {
+    PrimitiveDateTime::new(
+        Date::from_julian_day(2_451_545).expect("2000-01-01 is a valid date"),
+        Time::MIDNIGHT,
+    )
+}
+
+fn timestamp(buf: &[u8]) -> Option<PrimitiveDateTime> {
+    let us = i64::from_be_bytes(exact(buf)?);
+    epoch().checked_add(Duration::microseconds(us))
+}
+
+/// Returns the time of day and offset of a `TIMETZ`.
+fn time_tz(mut buf: &[u8]) -> Option<(Time, UtcOffset)> {
+    let us = i64::from_be_bytes(take(&mut buf)?);
+    // Postgres counts the offset in seconds west of UTC
+    let west = i32::from_be_bytes(exact(buf)?);
+    let offset = UtcOffset::from_whole_seconds(west.checked_neg()?).ok()?;
+
+    Some((Time::MIDNIGHT + Duration::microseconds(us), offset))
+}
+
+/// Returns the months, days and microseconds of an `INTERVAL`.
+fn interval(mut buf: &[u8]) -> Option<(i32, i32, i64)> {
+    let microseconds = i64::from_be_bytes(take(&mut buf)?);
+    let days = i32::from_be_bytes(take(&mut buf)?);
+    let months = i32::from_be_bytes(exact(buf)?);
+
+    Some((months, days, microseconds))
+}
+
+#[cfg(feature = "ipnetwork")]
+fn inet(mut buf: &[u8]) -> Option<sqlx::types::ipnetwork::IpNetwork> {
+    use std::net::IpAddr;
+
+    // the last byte is the address length, which `exact` checks below
+    let [family, prefix, _is_cidr, _len] = take(&mut buf)?;
+
+    let addr = match family {
+        // PGSQL_AF_INET
+        2 => IpAddr::from(exact::<4>(buf)?),
+        // PGSQL_AF_INET6
+        3 => IpAddr::from(exact::<16>(buf)?),
+        _ => return None,
+    };
+
+    sqlx::types::ipnetwork::IpNetwork::new(addr, prefix).ok()
+}
+
+/// Formats a `NUMERIC` as a plain decimal string, keeping its display scale.
+#[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+fn numeric(mut buf: &[u8]) -> Option<String> {
+    use std::fmt::Write;
+
+    let ndigits = i16::from_be_bytes(take(&mut buf)?);
+    let weight = i16::from_be_bytes(take(&mut buf)?);
+    let sign = u16::from_be_bytes(take(&mut buf)?);
+    let scale = u16::from_be_bytes(take(&mut buf)?);
+    let digits = (0..ndigits)
+        .map(|_| take(&mut buf).map(i16::from_be_bytes))
+        .collect::<Option<Vec<_>>>()?;
+
+    // base-10000 digit `i` is worth 10000^(weight - i)
+    let digit = |i: i32| {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| digits.get(i).copied())
+            .unwrap_or(0)
+    };
+
+    let mut s = String::new();
+
+    match sign {
+        0x0000 => {}
+        0x4000 => s.push('-'),
+        // NaN and the infinities
+        _ => return None,
+    }
+
+    if weight < 0 {
+        s.push('0');
+    } else {
+        write!(s, "{}", digit(0)).ok()?;
+
+        for i in 1..=i32::from(weight) {
+            write!(s, "{:04}", digit(i)).ok()?;
+        }
+    }
+
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut i = i32::from(weight) + 1;
+
+        while fraction.len() < usize::from(scale) {
+            write!(fraction, "{:04}", digit(i)).ok()?;
+            i += 1;
+        }
+
+        fraction.truncate(usize::from(scale));
+        s.push('.');
+        s.push_str(&fraction);
+    }
+
+    Some(s)
+}
+
+/// Splits `N` bytes off the front of `buf`.
+fn take<const N: usize>(buf: &mut &[u8]) -> Option<[u8; N]> {
+    let (head, rest) = buf.split_first_chunk()?;
+    *buf = rest;
+    Some(*head)
+}
+
+/// Splits a length-prefixed, non-null value off the front of `buf`.
+fn value<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = usize::try_from(i32::from_be_bytes(take(buf)?)).ok()?;
+    let (value, rest) = buf.split_at_checked(len)?;
+    *buf = rest;
+    Some(value)
+}
+
+/// Reads `buf` as exactly `N` bytes.
+fn exact<const N: usize>(buf: &[u8]) -> Option<[u8; N]> {
+    buf.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::UnsupportedTypePolicy;
+
+    /// Concatenates big-endian fields into a wire-format buffer.
+    fn wire(fields: &[&[u8]]) -> Vec<u8> {
+        fields.concat()
+    }
+
+    /// Converts an `INT4` array element.
+    fn int4(buf: &[u8]) -> Result<JsonValue, Error> {
+        to_json("INT4", buf, &Options::default(), &mut vec![])
+    }
+
+    #[test]
+    fn two_dimensional_array_with_null() {
+        // '{{1,NULL},{3,4}}'::INT4[]
+        let buf = wire(&[
+            &2i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &23u32.to_be_bytes(),
+            &2i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &2i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &(-1i32).to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &3i32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+        ]);
+
+        let v = array_to_json(&buf, &mut int4).unwrap();
+        assert_eq!(v, Some(json!([[1, null], [3, 4]])));
+
+        let truncated = array_to_json(&buf[..buf.len() - 2], &mut int4);
+        assert_eq!(truncated.unwrap(), None);
+    }
+
+    #[test]
+    fn array_with_lower_bound() {
+        // '[0:1]={1,2}'::INT4[], which sqlx's `Vec` decoder rejects
+        let buf = wire(&[
+            &1i32.to_be_bytes(),
+            &0i32.to_be_bytes(),
+            &23u32.to_be_bytes(),
+            &2i32.to_be_bytes(),
+            &0i32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &2i32.to_be_bytes(),
+        ]);
+
+        let v = array_to_json(&buf, &mut int4).unwrap();
+        assert_eq!(v, Some(json!([1, 2])));
+    }
+
+    #[test]
+    fn empty_array() {
+        let buf = wire(&[
+            &0i32.to_be_bytes(),
+            &0i32.to_be_bytes(),
+            &23u32.to_be_bytes(),
+        ]);
+
+        let v = array_to_json(&buf, &mut int4).unwrap();
+        assert_eq!(v, Some(json!([])));
+    }
+
+    #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+    fn numeric_wire(weight: i16, sign: u16, scale: u16, digits: &[i16]) -> Vec<u8> {
+        let ndigits = i16::try_from(digits.len()).unwrap();
+        let mut buf = wire(&[
+            &ndigits.to_be_bytes(),
+            &weight.to_be_bytes(),
+            &sign.to_be_bytes(),
+            &scale.to_be_bytes(),
+        ]);
+
+        for digit in digits {
+            buf.extend(digit.to_be_bytes());
+        }
+
+        buf
+    }
+
+    #[test]
+    #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+    fn numeric_negative_weight() {
+        // 0.0012
+        assert_eq!(
+            numeric(&numeric_wire(-1, 0, 4, &[12])).as_deref(),
+            Some("0.0012")
+        );
+        // -0.00000005
+        let buf = numeric_wire(-2, 0x4000, 8, &[5]);
+        assert_eq!(numeric(&buf).as_deref(), Some("-0.00000005"));
+    }
+
+    #[test]
+    #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+    fn numeric_display_scale() {
+        // 1.5 as a NUMERIC(10,1) and as a NUMERIC(10,4)
+        assert_eq!(
+            numeric(&numeric_wire(0, 0, 1, &[1, 5000])).as_deref(),
+            Some("1.5")
+        );
+        assert_eq!(
+            numeric(&numeric_wire(0, 0, 4, &[1, 5000])).as_deref(),
+            Some("1.5000")
+        );
+        // 12345678.90, whose trailing zero digits Postgres leaves out
+        let buf = numeric_wire(1, 0, 2, &[1234, 5678, 9000]);
+        assert_eq!(numeric(&buf).as_deref(), Some("12345678.90"));
+        // 20000 with its zero base-10000 digit left out
+        assert_eq!(
+            numeric(&numeric_wire(1, 0, 0, &[2])).as_deref(),
+            Some("20000")
+        );
+        assert_eq!(numeric(&numeric_wire(0, 0, 0, &[])).as_deref(), Some("0"));
+    }
+
+    #[test]
+    #[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+    fn numeric_nan() {
+        assert_eq!(numeric(&numeric_wire(0, 0xC000, 0, &[])), None);
+
+        let v = to_json(
+            "NUMERIC",
+            &numeric_wire(0, 0xC000, 0, &[]),
+            &Options::default(),
+            &mut vec![],
+        );
+        assert_eq!(v.unwrap(), JsonValue::Null);
+
+        let strict = Options {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(to_json(
+            "NUMERIC",
+            &numeric_wire(0, 0xC000, 0, &[]),
+            &strict,
+            &mut vec![]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn multirange_with_empty_and_bounded_range() {
+        // an empty range and [1,10); Postgres leaves empty ranges out of multiranges, but the
+        // format allows them
+        let buf = wire(&[
+            &2u32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &[RANGE_EMPTY],
+            &17i32.to_be_bytes(),
+            &[RANGE_LB_INC],
+            &4i32.to_be_bytes(),
+            &1i32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &10i32.to_be_bytes(),
+        ]);
+
+        let v = multirange_to_json("INT4", &buf, &Options::default(), &mut vec![]).unwrap();
+        assert_eq!(
+            v,
+            Some(json!([
+                {
+                    "lower": null,
+                    "upper": null,
+                    "lower_inclusive": false,
+                    "upper_inclusive": false,
+                    "empty": true,
+                },
+                {
+                    "lower": 1,
+                    "upper": 10,
+                    "lower_inclusive": true,
+                    "upper_inclusive": false,
+                    "empty": false,
+                },
+            ]))
+        );
+
+        let truncated = multirange_to_json(
+            "INT4",
+            &buf[..buf.len() - 1],
+            &Options::default(),
+            &mut vec![],
+        );
+        assert_eq!(truncated.unwrap(), None);
+    }
+
+    #[test]
+    fn fallbacks_in_nested_values() {
+        let options = Options {
+            unsupported_type: UnsupportedTypePolicy::Text,
+            ..Default::default()
+        };
+        let mut fallbacks = vec!["tsvector".to_owned()];
+
+        // a range with two bounds of an unsupported type, each recorded
+        let buf = wire(&[
+            &[RANGE_LB_INC],
+            &1i32.to_be_bytes(),
+            b"a",
+            &1i32.to_be_bytes(),
+            b"b",
+        ]);
+        let v = range_to_json("ltree", &buf, &options, &mut fallbacks).unwrap();
+        assert_eq!(v.unwrap()["upper"], json!("b"));
+        assert_eq!(fallbacks, ["tsvector", "ltree", "ltree"]);
+
+        // nothing is recorded when the fallback is an error
+        let mut fallbacks = vec![];
+        assert!(to_json("ltree", b"a", &Options::default(), &mut fallbacks).is_err());
+        assert!(fallbacks.is_empty());
+    }
+
+    #[test]
+    fn record_with_null_field() {
+        // ROW(7, NULL)
+        let buf = wire(&[
+            &2u32.to_be_bytes(),
+            &23u32.to_be_bytes(),
+            &4i32.to_be_bytes(),
+            &7i32.to_be_bytes(),
+            &25u32.to_be_bytes(),
+            &(-1i32).to_be_bytes(),
+        ]);
+
+        let fields = record_fields(&buf).unwrap();
+        assert_eq!(fields, [Some(&7i32.to_be_bytes()[..]), None]);
+
+        assert_eq!(record_fields(&buf[..buf.len() - 1]), None);
+        assert_eq!(record_fields(&[&buf[..], &[0]].concat()), None);
+    }
+
+    #[test]
+    fn multirange_with_unbounded_range() {
+        // '{(,5]}'::INT4MULTIRANGE
+        let buf = wire(&[
+            &1u32.to_be_bytes(),
+            &9i32.to_be_bytes(),
+            &[RANGE_LB_INF | RANGE_UB_INC],
+            &4i32.to_be_bytes(),
+            &5i32.to_be_bytes(),
+        ]);
+
+        let v = multirange_to_json("INT4", &buf, &Options::default(), &mut vec![])
+            .unwrap()
+            .unwrap();
+        assert_eq!(v[0]["lower"], JsonValue::Null);
+        assert_eq!(v[0]["upper"], json!(5));
+        assert_eq!(v[0]["upper_inclusive"], json!(true));
+    }
+}
@@ -0,0 +1,100 @@
+//! Postgres' built-in geometric types, which sqlx has no decoders for.
+
+use serde_json::{json, Value as JsonValue};
+
+use super::take;
+use crate::GeometryFormat;
+
+/// Converts a `POINT`, `LINE`, `LSEG`, `BOX`, `PATH`, `POLYGON` or `CIRCLE`.
+///
+/// Returns `None` if `buf` doesn't hold exactly one value of type `ty`.
+pub(super) fn to_json(ty: &str, mut buf: &[u8], format: GeometryFormat) -> Option<JsonValue> {
+    let buf = &mut buf;
+    let geojson = format == GeometryFormat::GeoJson;
+
+    let res = match ty {
+        "POINT" => {
+            let p = point(buf)?;
+
+            if geojson {
+                json!({ "type": "Point", "coordinates": p })
+            } else {
+                point_json(p)
+            }
+        }
+        // Ax + By + C = 0, which GeoJSON can't express
+        "LINE" => json!({ "a": float(buf)?, "b": float(buf)?, "c": float(buf)? }),
+        "LSEG" => {
+            let points = [point(buf)?, point(buf)?];
+
+            if geojson {
+                json!({ "type": "LineString", "coordinates": points })
+            } else {
+                points.into_iter().map(point_json).collect()
+            }
+        }
+        "BOX" => {
+            // Postgres stores the upper right corner first
+            let (high, low) = (point(buf)?, point(buf)?);
+
+            if geojson {
+                let [x1, y1] = low;
+                let [x2, y2] = high;
+                let ring = [low, [x2, y1], high, [x1, y2], low];
+                json!({ "type": "Polygon", "coordinates": [ring] })
+            } else {
+                json!({ "high": point_json(high), "low": point_json(low) })
+            }
+        }
+        "PATH" => {
+            let [closed] = take(buf)?;
+            let closed = closed != 0;
+            let mut points = points(buf)?;
+
+            if geojson {
+                if closed {
+                    points.extend(points.first().copied());
+                }
+
+                json!({ "type": "LineString", "coordinates": points })
+            } else {
+                let points: Vec<_> = points.into_iter().map(point_json).collect();
+                json!({ "closed": closed, "points": points })
+            }
+        }
+        "POLYGON" => {
+            let mut points = points(buf)?;
+
+            if geojson {
+                // GeoJSON rings repeat their first position at the end
+                points.extend(points.first().copied());
+                json!({ "type": "Polygon", "coordinates": [points] })
+            } else {
+                points.into_iter().map(point_json).collect()
+            }
+        }
+        // there is no circle in GeoJSON either
+        "CIRCLE" => json!({ "center": point_json(point(buf)?), "radius": float(buf)? }),
+        _ => return None,
+    };
+
+    buf.is_empty().then_some(res)
+}
+
+fn float(buf: &mut &[u8]) -> Option<f64> {
+    take(buf).map(f64::from_be_bytes)
+}
+
+fn point(buf: &mut &[u8]) -> Option<[f64; 2]> {
+    Some([float(buf)?, float(buf)?])
+}
+
+/// A point count followed by that many points, as in `PATH` and `POLYGON`.
+fn points(buf: &mut &[u8]) -> Option<Vec<[f64; 2]>> {
+    let len = i32::from_be_bytes(take(buf)?);
+    (0..len).map(|_| point(buf)).collect()
+}
+
+fn point_json([x, y]: [f64; 2]) -> JsonValue {
+    json!({ "x": x, "y": y })
+}